@@ -1,21 +1,16 @@
 extern crate pkg_config;
 extern crate gcc;
 
-#[macro_use]
 extern crate rust_cryptopp_gen as gen;
 
 use std::io;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::fs::File;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 use std::convert::From;
 
-fn find_cryptopp() -> pkg_config::Library {
-  match pkg_config::find_library("libcrypto++") {
-    Ok(info) => info,
-    Err(err) => panic!("failed to find cryptopp: {}", err)
-  }
-}
-
 #[derive(Debug)]
 pub enum Error {
   IO(io::Error),
@@ -37,93 +32,439 @@ impl From<gen::Error> for Error {
   }
 }
 
-fn gen_classes(mut ctx: gen::Context<File, File>,
-               out_path: &std::path::Path) -> gen::Result<()> {
-  use gen::proto::*;
-  let ctx_ptr = &mut ctx;
+/// colon (semicolon on windows) separated list of extra binding spec
+/// files. their classes are generated along with the ones of this
+/// crate, and the Rust structs and traits they ask for are exposed in
+/// the `extra` module.
+const EXTRA_SPECS_VAR: &'static str = "RUST_CRYPTOPP_EXTRA_SPECS";
+
+/// path of a file holding the output of a probe built and run by
+/// hand, for when the probe cannot run on the build host as when cross
+/// compiling.
+const PROBE_OUTPUT_VAR: &'static str = "RUST_CRYPTOPP_PROBE_OUTPUT";
+
+/// where the dynamic loader of the build host looks for shared
+/// libraries, besides its default directories.
+#[cfg(target_os = "windows")]
+const LOADER_PATH_VAR: &'static str = "PATH";
+#[cfg(target_os = "macos")]
+const LOADER_PATH_VAR: &'static str = "DYLD_LIBRARY_PATH";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const LOADER_PATH_VAR: &'static str = "LD_LIBRARY_PATH";
+
+/// the Crypto++ install to build against: `CRYPTOPP_DIR` holds its
+/// `include` and `lib` directories, or `CRYPTOPP_INCLUDE_DIR` and
+/// `CRYPTOPP_LIB_DIR` give them separately. when neither is set it is
+/// looked up with pkg-config, then in the usual prefixes.
+const DIR_VAR: &'static str = "CRYPTOPP_DIR";
+const INCLUDE_DIR_VAR: &'static str = "CRYPTOPP_INCLUDE_DIR";
+const LIB_DIR_VAR: &'static str = "CRYPTOPP_LIB_DIR";
+
+/// links Crypto++ statically when set to anything but `0`.
+const STATIC_VAR: &'static str = "CRYPTOPP_STATIC";
+
+/// the names distributions give the Crypto++ pkg-config package.
+const PKG_CONFIG_NAMES: &'static [&'static str] = &["libcrypto++", "libcryptopp", "cryptopp"];
+
+/// where Crypto++ gets installed when there is no pkg-config package.
+const PREFIXES: &'static [&'static str] = &["/usr/local", "/usr", "/opt/homebrew", "/opt/local"];
+
+/// the library names Crypto++ gets installed under, `libcryptopp.a` or
+/// `libcrypto++.so` for instance.
+const LIB_NAMES: &'static [&'static str] = &["cryptopp", "crypto++"];
+
+/// a Crypto++ install, see `find_cryptopp`.
+#[derive(Debug)]
+struct CryptoppLib {
+  include_paths: Vec<PathBuf>,
+  link_paths:    Vec<PathBuf>,
+  libs:          Vec<String>,
+}
+
+fn env_path(var: &str) -> Option<PathBuf> {
+  println!("cargo:rerun-if-env-changed={}", var);
+  env::var_os(var).map(PathBuf::from)
+}
+
+/// the name of the Crypto++ library in `dir`, if it has one.
+fn lib_name_in(dir: &Path, statik: bool) -> Option<&'static str> {
+  LIB_NAMES.iter().cloned().find(|name| {
+    let exts: &[&str] = if statik { &["a", "lib"] } else { &["so", "dylib", "a", "lib"] };
+    exts.iter().any(|ext| {
+      dir.join(format!("lib{}.{}", name, ext)).exists() ||
+        dir.join(format!("{}.{}", name, ext)).exists()
+    })
+  })
+}
+
+/// links the library found in `lib_dir` with headers in `include_dir`.
+fn cryptopp_lib_in(include_dir: PathBuf, lib_dir: PathBuf, statik: bool) -> Result<CryptoppLib> {
+  if !include_dir.join("cryptopp").join("cryptlib.h").exists() {
+    return Err(Error::Unexpected(format!("no cryptopp/cryptlib.h in {}", include_dir.display())));
+  }
+  let name = try!(lib_name_in(&lib_dir, statik).ok_or_else(|| {
+    Error::Unexpected(format!("no {}cryptopp library in {}",
+                              if statik { "static " } else { "" }, lib_dir.display()))
+  }));
+
+  println!("cargo:rustc-link-search=native={}", lib_dir.display());
+  println!("cargo:rustc-link-lib={}{}", if statik { "static=" } else { "" }, name);
+
+  Ok(CryptoppLib {
+    include_paths: vec![include_dir],
+    link_paths:    vec![lib_dir],
+    libs:          vec![String::from(name)],
+  })
+}
+
+/// the Debian multiarch directory of the libraries for `target`, such
+/// as `x86_64-linux-gnu` for `x86_64-unknown-linux-gnu`.
+fn multiarch_dir(target: &str) -> Option<String> {
+  let parts: Vec<&str> = target.split('-').collect();
+  if parts.len() != 4 || parts[2] != "linux" {
+    return None;
+  }
+
+  let arch = match parts[0] {
+    "i386" | "i586" | "i686"                                     => "i386",
+    "riscv64gc"                                                  => "riscv64",
+    arch if arch.starts_with("arm") || arch.starts_with("thumb") => "arm",
+    arch                                                         => arch
+  };
+
+  Some(format!("{}-linux-{}", arch, parts[3]))
+}
+
+/// finds Crypto++ and tells cargo how to link it, see `DIR_VAR`.
+fn find_cryptopp() -> Result<CryptoppLib> {
+  println!("cargo:rerun-if-env-changed={}", STATIC_VAR);
+  let statik = env::var_os(STATIC_VAR).map_or(false, |v| v != "0");
+  let dir = env_path(DIR_VAR);
+  let include_dir = env_path(INCLUDE_DIR_VAR);
+  let lib_dir = env_path(LIB_DIR_VAR);
+
+  if dir.is_some() || include_dir.is_some() || lib_dir.is_some() {
+    let include_dir = include_dir.or(dir.as_ref().map(|d| d.join("include")));
+    let lib_dir = lib_dir.or(dir.as_ref().map(|d| d.join("lib")));
+    return match (include_dir, lib_dir) {
+      (Some(include_dir), Some(lib_dir)) => cryptopp_lib_in(include_dir, lib_dir, statik),
+      _                                  => Err(Error::Unexpected(format!(
+        "{} and {} must be set together, or {} instead", INCLUDE_DIR_VAR, LIB_DIR_VAR, DIR_VAR)))
+    };
+  }
 
-  let ht = class!(b"HashTransformation" => {
-    constant methods {
-      uint(), b"DigestSize";
+  let mut tried = Vec::new();
+  for name in PKG_CONFIG_NAMES.iter() {
+    match pkg_config::Config::new().statik(statik).probe(name) {
+      Ok(lib)  => return Ok(CryptoppLib {
+        include_paths: lib.include_paths,
+        link_paths:    lib.link_paths,
+        libs:          lib.libs,
+      }),
+      Err(err) => tried.push(format!("pkg-config {}: {}", name, err))
     }
-    mutable methods {
-      void(), b"Update",     const_ptr(UChar), size_t();
-      void(), b"Final",      mut_ptr(UChar);
-      void(), b"Restart";
+  }
+
+  let mut lib_dirs = vec![String::from("lib"), String::from("lib64")];
+  if let Some(multiarch) = multiarch_dir(&env::var("TARGET").unwrap()) {
+    lib_dirs.push(format!("lib/{}", multiarch));
+  }
+  for prefix in PREFIXES.iter() {
+    let prefix = Path::new(prefix);
+    for lib_dir in lib_dirs.iter() {
+      let lib_dir = prefix.join(lib_dir);
+      if lib_name_in(&lib_dir, statik).is_some() {
+        match cryptopp_lib_in(prefix.join("include"), lib_dir, statik) {
+          Ok(lib)  => return Ok(lib),
+          Err(err) => tried.push(format!("{:?}", err))
+        }
+      }
     }
-  });
-  try!(ht.generate_bindings(ctx_ptr));
+  }
+  tried.push(format!("no library in {} under {}", lib_dirs.join(", "), PREFIXES.join(", ")));
+
+  Err(Error::Unexpected(format!("failed to find cryptopp, set {} (or {} and {}) to its \
+                                 install prefix. tried:\n  {}",
+                                DIR_VAR, INCLUDE_DIR_VAR, LIB_DIR_VAR, tried.join("\n  "))))
+}
+
+/// Crypto++ versions bringing algorithms the crate may bind: SHA3 and
+/// BLAKE2 in 5.6.4, `LibraryVersion` in 6.0, ed25519 and x25519 in
+/// 8.0, SHAKE in 8.1, XTS in 8.3 and LSH in 8.6. the `since` versions
+/// of the specs are added to these.
+const VERSIONS: &'static [u32] = &[564, 600, 800, 810, 830, 860];
+
+/// the cfg set when the Crypto++ headers are at least `version`, such
+/// as `cryptopp_5_6_4` or `cryptopp_8_0`.
+fn version_cfg(version: u32) -> String {
+  let (major, minor, patch) = (version / 100, version / 10 % 10, version % 10);
+  if patch == 0 {
+    format!("cryptopp_{}_{}", major, minor)
+  } else {
+    format!("cryptopp_{}_{}_{}", major, minor, patch)
+  }
+}
+
+/// reads `CRYPTOPP_VERSION` from the headers, `config_ver.h` since 8.0
+/// and `config.h` before.
+fn header_version(cryptopp_lib: &CryptoppLib) -> Result<u32> {
+  for include_path in cryptopp_lib.include_paths.iter() {
+    for header in ["config_ver.h", "config.h"].iter() {
+      let path = include_path.join("cryptopp").join(header);
+      let mut src = String::new();
+      match File::open(&path) {
+        Ok(mut file) => try!(file.read_to_string(&mut src)),
+        Err(_)       => continue
+      };
+      println!("cargo:rerun-if-changed={}", path.display());
 
-  let hash = prototype_class!({
-    constructors {
-      b"";
+      for line in src.lines() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.len() == 3 && words[0] == "#define" && words[1] == "CRYPTOPP_VERSION" {
+          return words[2].parse().map_err(|_| {
+            Error::Unexpected(format!("bad CRYPTOPP_VERSION `{}` in {}", words[2], path.display()))
+          });
+        }
+      }
     }
-  });
+  }
 
-  macro_rules! gen_hash {
-    () => ();
+  Err(Error::Unexpected(String::from("no CRYPTOPP_VERSION in the cryptopp headers")))
+}
 
-    ($cpp_name:expr, $( $rest:tt )* ) => ({
-      let concrete_hash = class!(vec![], $cpp_name, &hash);
-      try!(concrete_hash.generate_bindings(ctx_ptr));
-      try!(concrete_hash.generate_struct(out_path, b"Hash"));
-      gen_hash!($( $rest )*);
-    })
+/// sets the cfg of each of `versions` up to `version`, see
+/// `version_cfg`, and writes `version.rs`, declaring the
+/// `HEADER_VERSION` the crate is built against.
+fn gen_version(version: u32, versions: &[u32], out_path: &Path) -> Result<()> {
+  for &since in versions.iter() {
+    let cfg = version_cfg(since);
+    println!("cargo:rustc-check-cfg=cfg({})", cfg);
+    if version >= since {
+      println!("cargo:rustc-cfg={}", cfg);
+    }
   }
 
-  gen_hash!(b"SHA1",
-            b"SHA3_224",
-            b"SHA3_256",
-            b"SHA3_384",
-            b"SHA3_512",);
+  let mut stream = try!(File::create(out_path.join("version.rs")));
+  try!(write!(stream, "pub const HEADER_VERSION: u32 = {};\n", version));
 
-  class!(b"Integer" => {
-    constructors {
-      b"";
-      b"copy",      const_ref(Custom(b"Integer"));
-      b"from_long", long();
+  Ok(())
+}
+
+fn cpp_config(cryptopp_lib: &CryptoppLib) -> gcc::Config {
+  let mut config = gcc::Config::new();
+  config.cpp(true);
+  for path in cryptopp_lib.include_paths.iter() {
+    config.include(path);
+  }
+
+  config
+}
+
+/// builds and runs the probe printing the values of the enums and
+/// constants of `spec`, see `gen::probe`. there is no probe when
+/// `spec` has none, and it is only written out when cross compiling.
+fn run_probe(spec: &gen::spec::Spec,
+             out_path: &Path,
+             cryptopp_lib: &CryptoppLib) -> Result<gen::probe::Values> {
+  if !spec.has_consts() {
+    return Ok(try!(gen::probe::Values::parse("")));
+  }
+
+  println!("cargo:rerun-if-env-changed={}", PROBE_OUTPUT_VAR);
+  if let Some(path) = env::var_os(PROBE_OUTPUT_VAR) {
+    let mut output = String::new();
+    try!(try!(File::open(&path)).read_to_string(&mut output));
+    return Ok(try!(gen::probe::Values::parse(&output)));
+  }
+
+  let probe_src = out_path.join("probe.cpp");
+  let probe_exe = out_path.join("probe").with_extension(env::consts::EXE_EXTENSION);
+  try!(spec.generate_probe(try!(File::create(&probe_src))));
+
+  let target = env::var("TARGET").unwrap();
+  let host = env::var("HOST").unwrap();
+  if target != host {
+    return Err(Error::Unexpected(format!(
+      "the probe cannot run on {} when building for {}. build {} for the target, run it \
+       there and set {} to the path of a file holding its output",
+      host, target, probe_src.display(), PROBE_OUTPUT_VAR)));
+  }
+
+  // the gcc crate picks cl.exe for msvc targets, which has flags of
+  // its own.
+  let mut cmd = cpp_config(cryptopp_lib).get_compiler().to_command();
+  if target.contains("msvc") {
+    cmd.arg("/EHsc")
+       .arg(&probe_src)
+       .arg(format!("/Fo{}\\", out_path.display()))
+       .arg(format!("/Fe{}", probe_exe.display()))
+       .arg("/link");
+    for path in cryptopp_lib.link_paths.iter() {
+      cmd.arg(format!("/LIBPATH:{}", path.display()));
+    }
+    for lib in cryptopp_lib.libs.iter() {
+      cmd.arg(format!("{}.lib", lib));
     }
-  }).generate_bindings(ctx_ptr)
+  } else {
+    cmd.arg(&probe_src).arg("-o").arg(&probe_exe);
+    for path in cryptopp_lib.link_paths.iter() {
+      cmd.arg(format!("-L{}", path.display()));
+    }
+    for lib in cryptopp_lib.libs.iter() {
+      cmd.arg(format!("-l{}", lib));
+    }
+  }
+
+  let status = try!(cmd.status());
+  if !status.success() {
+    return Err(Error::Unexpected(format!("failed to compile the probe: {:?}", cmd)));
+  }
+
+  // a shared cryptopp outside the default search path is only found
+  // through the loader path.
+  let loader_path = env::var_os(LOADER_PATH_VAR);
+  let loader_paths = cryptopp_lib.link_paths.iter().cloned()
+    .chain(loader_path.iter().flat_map(env::split_paths));
+  let loader_path = try!(env::join_paths(loader_paths).map_err(|e| {
+    Error::Unexpected(format!("bad {}: {}", LOADER_PATH_VAR, e))
+  }));
+
+  let output = try!(Command::new(&probe_exe).env(LOADER_PATH_VAR, loader_path).output());
+  if !output.status.success() {
+    return Err(Error::Unexpected(format!("the probe failed: {}",
+                                         String::from_utf8_lossy(&output.stderr))));
+  }
+
+  Ok(try!(gen::probe::Values::parse(&String::from_utf8_lossy(&output.stdout))))
 }
 
-fn gen_cpp_code(cpp_path: &std::path::Path,
-                rust_binding_path: &std::path::Path,
-                out_path: &std::path::Path) -> Result<()> {
+/// whether the cargo feature `name` is enabled for this build.
+fn feature_enabled(name: &str) -> bool {
+  let var = format!("CARGO_FEATURE_{}", name.to_uppercase().replace("-", "_"));
+  env::var_os(var).is_some()
+}
+
+fn load_specs(manifest_path: &Path) -> Result<(gen::spec::Spec, gen::spec::Spec)> {
+  let bindings_path = manifest_path.join("bindings.toml");
+  println!("cargo:rerun-if-changed={}", bindings_path.display());
+  println!("cargo:rerun-if-env-changed={}", EXTRA_SPECS_VAR);
+
+  let spec = try!(gen::spec::Spec::load(&bindings_path));
+
+  let mut extra = gen::spec::Spec::new();
+  if let Some(paths) = env::var_os(EXTRA_SPECS_VAR) {
+    for path in env::split_paths(&paths) {
+      println!("cargo:rerun-if-changed={}", path.display());
+      extra.extend(try!(gen::spec::Spec::load(&path)));
+    }
+  }
+
+  Ok((spec, extra))
+}
+
+/// writes a module for each struct, trait or consts file of the extra
+/// specs.
+fn gen_extra_modules(extra: &gen::spec::Spec, out_path: &Path) -> Result<()> {
+  let mut stream = try!(File::create(out_path.join("extra.rs")));
+
+  for cls in extra.classes.iter() {
+    for named in cls.named().iter() {
+      let c_path = try!(named.c_path());
+      let mut files = Vec::new();
+      if cls.class.has_consts() {
+        files.push(format!("consts_{}.rs", c_path));
+      }
+      if cls.struct_name.is_some() {
+        files.push(format!("{}.rs", c_path));
+      }
+      if cls.trait_name.is_some() {
+        files.push(format!("rust_{}.rs", c_path));
+      }
+      if files.len() < 1 {
+        continue;
+      }
+
+      try!(write!(stream, "#[allow(non_snake_case)]\npub mod {} {{\n", c_path));
+      try!(stream.write_all(b"  #[allow(unused_imports)]\n  use libc::c_void;\n  use cpp;\n\n"));
+      for file in files.iter() {
+        try!(write!(stream, "  include!(concat!(env!(\"OUT_DIR\"), \"/{}\"));\n", file));
+      }
+      try!(stream.write_all(b"}\n\n"));
+    }
+  }
+
+  Ok(())
+}
+
+fn gen_cpp_code(cpp_path: &Path,
+                rust_binding_path: &Path,
+                out_path: &Path,
+                manifest_path: &Path,
+                cryptopp_lib: &CryptoppLib) -> Result<()> {
+
+  let (mut spec, mut extra) = try!(load_specs(manifest_path));
+  let version = try!(header_version(cryptopp_lib));
+  let mut versions = VERSIONS.to_vec();
+  versions.extend(spec.versions());
+  versions.extend(extra.versions());
+  versions.sort();
+  versions.dedup();
+  try!(gen_version(version, &versions, out_path));
+
+  // the extra modules only include the files of classes that are
+  // generated, so they are written once the extra specs are filtered.
+  spec.select_features(feature_enabled);
+  spec.select_version(version);
+  extra.select_features(feature_enabled);
+  extra.select_version(version);
+  try!(gen_extra_modules(&extra, out_path));
+  spec.extend(extra);
+
+  let values = try!(run_probe(&spec, out_path, cryptopp_lib));
+  try!(spec.generate_consts(out_path, &values));
 
   let mut cpp_stream = try!(File::create(cpp_path));
 
-  try!(cpp_stream.write_all(b"#include <cryptopp/cryptlib.h>\n"));
-  try!(cpp_stream.write_all(b"#include <cryptopp/sha.h>\n"));
-  try!(cpp_stream.write_all(b"#include <cryptopp/sha3.h>\n"));
-  try!(cpp_stream.write_all(b"#include <cryptopp/integer.h>\n"));
+  try!(spec.write_includes(&mut cpp_stream));
   try!(cpp_stream.write_all(b"using namespace CryptoPP;\n\n"));
+  try!(gen::generate_cpp_prelude(&mut cpp_stream));
 
   let mut rs_binding_stream = try!(File::create(rust_binding_path));
   try!(gen::generate_prelude(&mut rs_binding_stream));
   try!(rs_binding_stream.write_all(b"#[link(name = \"rustcryptopp\")]\n"));
 
-  let ctx = gen::Context::new(cpp_stream, rs_binding_stream);
-  try!(gen_classes(ctx, out_path));
+  let mut c_header_stream = try!(File::create(out_path.join("rustcryptopp.h")));
+  try!(gen::generate_c_header_prelude(&mut c_header_stream));
+
+  let mut ctx = gen::Context::with_c_header(cpp_stream, rs_binding_stream, c_header_stream);
+  try!(spec.generate(&mut ctx, out_path));
+  try!(gen::generate_c_header_end(&mut ctx.c_header_stream));
 
   Ok(())
 }
  
 fn main() {
-  let cryptopp_lib = find_cryptopp();
+  let cryptopp_lib = match find_cryptopp() {
+    Ok(lib)                     => lib,
+    Err(Error::Unexpected(msg)) => panic!("{}", msg),
+    Err(e)                      => panic!("failed to find cryptopp: {:?}", e)
+  };
   println!("cryptopp: {:?}", cryptopp_lib);
 
-  let out_dir = std::env::var("OUT_DIR").unwrap();
-  let out_path = std::path::Path::new(&out_dir);
+  let out_dir = env::var("OUT_DIR").unwrap();
+  let out_path = Path::new(&out_dir);
+  let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
   let rust_binding_src = out_path.join("generated_bindings.rs");
   let cpp_src = out_path.join("generated_cpp.cpp");
 
-  if let Err(e) = gen_cpp_code(&cpp_src, &rust_binding_src, out_path) {
-    println!("error: {:?}\n", e);
-    return;
+  match gen_cpp_code(&cpp_src, &rust_binding_src, out_path,
+                     Path::new(&manifest_dir), &cryptopp_lib) {
+    Ok(())                      => (),
+    Err(Error::Unexpected(msg)) => panic!("failed to generate the bindings: {}", msg),
+    Err(e)                      => panic!("failed to generate the bindings: {:?}", e)
   }
 
-  let mut config = gcc::Config::new();
-  config.cpp(true);
+  let mut config = cpp_config(&cryptopp_lib);
 
   config.file(&cpp_src);
 
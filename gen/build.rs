@@ -0,0 +1,23 @@
+use std::env;
+use std::process::Command;
+
+/// with the `clang` feature, tells the linker where to find libclang:
+/// in `LIBCLANG_PATH` if set, else in `llvm-config --libdir`.
+fn main() {
+  if env::var_os("CARGO_FEATURE_CLANG").is_none() {
+    return;
+  }
+
+  println!("cargo:rerun-if-env-changed=LIBCLANG_PATH");
+
+  let libdir = match env::var("LIBCLANG_PATH") {
+    Ok(path) => Some(path),
+    Err(_)   => Command::new("llvm-config").arg("--libdir").output().ok()
+      .and_then(|out| if out.status.success() { String::from_utf8(out.stdout).ok() } else { None })
+      .map(|dir| String::from(dir.trim()))
+  };
+
+  if let Some(dir) = libdir {
+    println!("cargo:rustc-link-search=native={}", dir);
+  }
+}
@@ -0,0 +1,868 @@
+//! builds a `Spec` by parsing the Crypto++ headers with libclang
+//! instead of describing each class by hand. enabled by the `clang`
+//! feature, which links against libclang; set `LIBCLANG_PATH` if it is
+//! not on the linker path.
+//!
+//! ```ignore
+//! let mut parser = gen::clang::HeaderParser::new();
+//! parser.add_include_path("/usr/include");
+//! parser.add_header("cryptopp/sha.h");
+//! parser.allow_class("HashTransformation", None);
+//! parser.allow_class("SHA256", Some("Hash"));
+//! let parsed = try!(parser.parse());
+//! ```
+//!
+//! only the allowed classes are bound: their public constructors,
+//! methods, static methods, operators, enums and integer constants.
+//! an allowed class derived from another one gets it as a base.
+//! a class or a type used by a binding is named as in the allowlist,
+//! or else by its name without the `CryptoPP::` namespace.
+//!
+//! members that cannot be bound are skipped, each with an
+//! `Error::Clang` describing it in `Parsed::skipped`.
+
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::path::Path;
+use std::ptr;
+
+use proto;
+use proto::{BasicType, CType};
+use spec::{ClassSpec, Spec, leak_str, parse_class_path, primitive};
+use {Class, FunctionArgs, Function, Operator, Error, Result, class};
+
+#[allow(non_camel_case_types, non_upper_case_globals, dead_code)]
+mod ffi {
+  use std::os::raw::{c_char, c_int, c_uint, c_ulong, c_void};
+
+  pub type CXIndex = *mut c_void;
+  pub type CXTranslationUnit = *mut c_void;
+  pub type CXDiagnostic = *mut c_void;
+  pub type CXFile = *mut c_void;
+  pub type CXClientData = *mut c_void;
+
+  #[repr(C)]
+  #[derive(Clone, Copy)]
+  pub struct CXString {
+    data: *const c_void,
+    private_flags: c_uint
+  }
+
+  #[repr(C)]
+  #[derive(Clone, Copy)]
+  pub struct CXCursor {
+    pub kind: c_int,
+    xdata: c_int,
+    data: [*const c_void; 3]
+  }
+
+  #[repr(C)]
+  #[derive(Clone, Copy)]
+  pub struct CXType {
+    pub kind: c_int,
+    data: [*mut c_void; 2]
+  }
+
+  #[repr(C)]
+  pub struct CXSourceLocation {
+    ptr_data: [*const c_void; 2],
+    int_data: c_uint
+  }
+
+  #[repr(C)]
+  pub struct CXUnsavedFile {
+    pub filename: *const c_char,
+    pub contents: *const c_char,
+    pub length: c_ulong
+  }
+
+  pub type CXCursorVisitor = extern "C" fn(CXCursor, CXCursor, CXClientData) -> c_int;
+
+  pub const CXChildVisit_Continue: c_int = 1;
+
+  pub const CXTranslationUnit_SkipFunctionBodies: c_uint = 0x40;
+
+  pub const CXDiagnostic_Error: c_int = 3;
+
+  pub const CX_CXXPublic: c_int = 1;
+
+  pub const CXCursor_StructDecl: c_int = 2;
+  pub const CXCursor_ClassDecl: c_int = 4;
+  pub const CXCursor_EnumDecl: c_int = 5;
+  pub const CXCursor_EnumConstantDecl: c_int = 7;
+  pub const CXCursor_FunctionDecl: c_int = 8;
+  pub const CXCursor_VarDecl: c_int = 9;
+  pub const CXCursor_TypedefDecl: c_int = 20;
+  pub const CXCursor_CXXMethod: c_int = 21;
+  pub const CXCursor_Namespace: c_int = 22;
+  pub const CXCursor_LinkageSpec: c_int = 23;
+  pub const CXCursor_Constructor: c_int = 24;
+  pub const CXCursor_ConversionFunction: c_int = 26;
+  pub const CXCursor_FunctionTemplate: c_int = 30;
+  pub const CXCursor_TypeAliasDecl: c_int = 36;
+  pub const CXCursor_CXXBaseSpecifier: c_int = 44;
+  pub const CXCursor_TranslationUnit: c_int = 300;
+
+  pub const CXType_Void: c_int = 2;
+  pub const CXType_Bool: c_int = 3;
+  pub const CXType_Char_U: c_int = 4;
+  pub const CXType_UChar: c_int = 5;
+  pub const CXType_UInt: c_int = 9;
+  pub const CXType_ULongLong: c_int = 11;
+  pub const CXType_Char_S: c_int = 13;
+  pub const CXType_Int: c_int = 17;
+  pub const CXType_Long: c_int = 18;
+  pub const CXType_LongLong: c_int = 19;
+  pub const CXType_Double: c_int = 22;
+  pub const CXType_Pointer: c_int = 101;
+  pub const CXType_LValueReference: c_int = 103;
+  pub const CXType_RValueReference: c_int = 104;
+  pub const CXType_Record: c_int = 105;
+  pub const CXType_Enum: c_int = 106;
+  pub const CXType_Typedef: c_int = 107;
+  pub const CXType_Elaborated: c_int = 119;
+
+  #[link(name = "clang")]
+  extern "C" {
+    pub fn clang_createIndex(exclude_pch: c_int, display_diagnostics: c_int) -> CXIndex;
+    pub fn clang_disposeIndex(index: CXIndex);
+    pub fn clang_parseTranslationUnit(index: CXIndex,
+                                      source_filename: *const c_char,
+                                      command_line_args: *const *const c_char,
+                                      num_command_line_args: c_int,
+                                      unsaved_files: *mut CXUnsavedFile,
+                                      num_unsaved_files: c_uint,
+                                      options: c_uint) -> CXTranslationUnit;
+    pub fn clang_disposeTranslationUnit(tu: CXTranslationUnit);
+
+    pub fn clang_getNumDiagnostics(tu: CXTranslationUnit) -> c_uint;
+    pub fn clang_getDiagnostic(tu: CXTranslationUnit, index: c_uint) -> CXDiagnostic;
+    pub fn clang_getDiagnosticSeverity(diag: CXDiagnostic) -> c_int;
+    pub fn clang_formatDiagnostic(diag: CXDiagnostic, options: c_uint) -> CXString;
+    pub fn clang_defaultDiagnosticDisplayOptions() -> c_uint;
+    pub fn clang_disposeDiagnostic(diag: CXDiagnostic);
+
+    pub fn clang_getCString(s: CXString) -> *const c_char;
+    pub fn clang_disposeString(s: CXString);
+
+    pub fn clang_getTranslationUnitCursor(tu: CXTranslationUnit) -> CXCursor;
+    pub fn clang_visitChildren(parent: CXCursor,
+                               visitor: CXCursorVisitor,
+                               data: CXClientData) -> c_uint;
+    pub fn clang_Cursor_isNull(cursor: CXCursor) -> c_int;
+    pub fn clang_isCursorDefinition(cursor: CXCursor) -> c_uint;
+    pub fn clang_getCursorDefinition(cursor: CXCursor) -> CXCursor;
+    pub fn clang_getCursorSemanticParent(cursor: CXCursor) -> CXCursor;
+    pub fn clang_getCursorSpelling(cursor: CXCursor) -> CXString;
+    pub fn clang_getCursorLocation(cursor: CXCursor) -> CXSourceLocation;
+    pub fn clang_getSpellingLocation(location: CXSourceLocation,
+                                     file: *mut CXFile,
+                                     line: *mut c_uint,
+                                     column: *mut c_uint,
+                                     offset: *mut c_uint);
+    pub fn clang_getFileName(file: CXFile) -> CXString;
+    pub fn clang_getCXXAccessSpecifier(cursor: CXCursor) -> c_int;
+
+    pub fn clang_getCursorType(cursor: CXCursor) -> CXType;
+    pub fn clang_getCursorResultType(cursor: CXCursor) -> CXType;
+    pub fn clang_Cursor_getNumArguments(cursor: CXCursor) -> c_int;
+    pub fn clang_Cursor_getArgument(cursor: CXCursor, i: c_uint) -> CXCursor;
+    pub fn clang_getTypedefDeclUnderlyingType(cursor: CXCursor) -> CXType;
+    pub fn clang_CXXMethod_isStatic(cursor: CXCursor) -> c_uint;
+    pub fn clang_CXXMethod_isConst(cursor: CXCursor) -> c_uint;
+    pub fn clang_CXXRecord_isAbstract(cursor: CXCursor) -> c_uint;
+
+    pub fn clang_getTypeSpelling(t: CXType) -> CXString;
+    pub fn clang_getCanonicalType(t: CXType) -> CXType;
+    pub fn clang_getPointeeType(t: CXType) -> CXType;
+    pub fn clang_isConstQualifiedType(t: CXType) -> c_uint;
+    pub fn clang_getTypeDeclaration(t: CXType) -> CXCursor;
+    pub fn clang_Type_getNamedType(t: CXType) -> CXType;
+    pub fn clang_Type_getNumTemplateArguments(t: CXType) -> c_int;
+    pub fn clang_isFunctionTypeVariadic(t: CXType) -> c_uint;
+  }
+}
+
+use self::ffi::{CXCursor, CXType};
+
+fn to_string(s: ffi::CXString) -> String {
+  unsafe {
+    let p = ffi::clang_getCString(s);
+    let owned = if p.is_null() {
+      String::new()
+    } else {
+      CStr::from_ptr(p).to_string_lossy().into_owned()
+    };
+    ffi::clang_disposeString(s);
+
+    owned
+  }
+}
+
+fn spelling(cursor: CXCursor) -> String {
+  to_string(unsafe { ffi::clang_getCursorSpelling(cursor) })
+}
+
+fn type_spelling(t: CXType) -> String {
+  to_string(unsafe { ffi::clang_getTypeSpelling(t) })
+}
+
+fn is_null(cursor: CXCursor) -> bool {
+  unsafe { ffi::clang_Cursor_isNull(cursor) != 0 }
+}
+
+fn is_public(cursor: CXCursor) -> bool {
+  unsafe { ffi::clang_getCXXAccessSpecifier(cursor) == ffi::CX_CXXPublic }
+}
+
+extern "C" fn collect_child(cursor: CXCursor, _: CXCursor, data: ffi::CXClientData) -> i32 {
+  let children = unsafe { &mut *(data as *mut Vec<CXCursor>) };
+  children.push(cursor);
+
+  ffi::CXChildVisit_Continue
+}
+
+fn children(cursor: CXCursor) -> Vec<CXCursor> {
+  let mut children: Vec<CXCursor> = Vec::new();
+  unsafe {
+    ffi::clang_visitChildren(cursor, collect_child,
+                             &mut children as *mut Vec<CXCursor> as ffi::CXClientData);
+  }
+
+  children
+}
+
+/// the name of the declaration with its namespaces and enclosing
+/// classes, `CryptoPP::Integer::Signedness`.
+fn qualified_name(cursor: CXCursor) -> String {
+  let mut parts = vec![spelling(cursor)];
+  let mut parent = unsafe { ffi::clang_getCursorSemanticParent(cursor) };
+  while !is_null(parent) && parent.kind != ffi::CXCursor_TranslationUnit {
+    parts.push(spelling(parent));
+    parent = unsafe { ffi::clang_getCursorSemanticParent(parent) };
+  }
+  parts.reverse();
+
+  parts.join("::")
+}
+
+/// `file:line`, for diagnostics.
+fn location(cursor: CXCursor) -> String {
+  let mut file: ffi::CXFile = ptr::null_mut();
+  let mut line = 0;
+  unsafe {
+    ffi::clang_getSpellingLocation(ffi::clang_getCursorLocation(cursor),
+                                   &mut file, &mut line, ptr::null_mut(), ptr::null_mut());
+  }
+
+  if file.is_null() {
+    String::from("<unknown>")
+  } else {
+    format!("{}:{}", to_string(unsafe { ffi::clang_getFileName(file) }), line)
+  }
+}
+
+/// the definition of the class declared by `t`, if it is one.
+fn class_definition(t: CXType) -> Option<CXCursor> {
+  let canonical = unsafe { ffi::clang_getCanonicalType(t) };
+  if canonical.kind != ffi::CXType_Record {
+    return None;
+  }
+
+  let decl = unsafe { ffi::clang_getCursorDefinition(ffi::clang_getTypeDeclaration(canonical)) };
+  if is_null(decl) { None } else { Some(decl) }
+}
+
+/// whether an allowlist entry names the class qualified as `qualified`.
+/// `SHA256` names `CryptoPP::SHA256`.
+fn allowlist_match(entry: &str, qualified: &str) -> bool {
+  entry == qualified || (qualified.starts_with("CryptoPP::") && &qualified[10..] == entry)
+}
+
+/// the operator declared by a function spelled `name`, such as
+/// `operator+=`.
+fn operator_token(name: &str) -> Option<&str> {
+  if name.starts_with("operator") {
+    let token = name[8..].trim();
+    if token.len() > 0 && !token.chars().next().unwrap().is_alphanumeric() {
+      return Some(token);
+    }
+  }
+
+  None
+}
+
+/// `name` for the first overload, then `name_2`, `name_3`...
+fn overload_alias(counts: &mut HashMap<String, usize>, name: &str) -> &'static [u8] {
+  let count = counts.entry(String::from(name)).or_insert(0);
+  *count += 1;
+
+  if *count == 1 {
+    leak_str(name).as_bytes()
+  } else {
+    leak_str(&format!("{}_{}", name, count)).as_bytes()
+  }
+}
+
+struct Index(ffi::CXIndex);
+
+impl Drop for Index {
+  fn drop(&mut self) {
+    unsafe { ffi::clang_disposeIndex(self.0) };
+  }
+}
+
+struct TranslationUnit(ffi::CXTranslationUnit);
+
+impl Drop for TranslationUnit {
+  fn drop(&mut self) {
+    unsafe { ffi::clang_disposeTranslationUnit(self.0) };
+  }
+}
+
+/// the result of `HeaderParser::parse`.
+pub struct Parsed {
+  /// the allowed classes, in the order of the allowlist.
+  pub spec:    Spec,
+  /// what could not be bound, as `Error::Clang`.
+  pub skipped: Vec<Error>,
+}
+
+pub struct HeaderParser {
+  headers:   Vec<String>,
+  args:      Vec<String>,
+  allowlist: Vec<(String, Option<&'static [u8]>)>,
+}
+
+impl HeaderParser {
+  pub fn new() -> HeaderParser {
+    HeaderParser {
+      headers:   Vec::new(),
+      args:      vec![String::from("-x"), String::from("c++")],
+      allowlist: Vec::new(),
+    }
+  }
+
+  /// adds a header to parse, spelled as in an `#include <...>`.
+  pub fn add_header(&mut self, header: &str) {
+    self.headers.push(String::from(header));
+  }
+
+  pub fn add_include_path<P: AsRef<Path>>(&mut self, path: P) {
+    self.args.push(format!("-I{}", path.as_ref().display()));
+  }
+
+  /// adds an argument to the libclang command line, such as `-std=c++11`.
+  pub fn add_arg(&mut self, arg: &str) {
+    self.args.push(String::from(arg));
+  }
+
+  /// binds the class `name`, and writes a Rust struct named
+  /// `struct_name` for it if given, see `ClassSpec`.
+  pub fn allow_class(&mut self, name: &str, struct_name: Option<&str>) {
+    self.allowlist.push((String::from(name), struct_name.map(|s| leak_str(s).as_bytes())));
+  }
+
+  pub fn parse(&self) -> Result<Parsed> {
+    let mut source = String::new();
+    for header in self.headers.iter() {
+      source.push_str(&format!("#include <{}>\n", header));
+    }
+
+    let args: Vec<CString> = try!(self.args.iter().map(|arg| {
+      CString::new(&arg[..]).map_err(|_| Error::Clang(format!("bad argument `{}`", arg)))
+    }).collect());
+    let arg_ptrs: Vec<_> = args.iter().map(|arg| arg.as_ptr()).collect();
+    let filename = CString::new("rcpp_headers.cpp").unwrap();
+    let contents = try!(CString::new(source).map_err(|_| Error::Clang(String::from("bad header name"))));
+    let mut unsaved = ffi::CXUnsavedFile {
+      filename: filename.as_ptr(),
+      contents: contents.as_ptr(),
+      length:   contents.as_bytes().len() as _,
+    };
+
+    let index = Index(unsafe { ffi::clang_createIndex(0, 0) });
+    let tu = TranslationUnit(unsafe {
+      ffi::clang_parseTranslationUnit(index.0, filename.as_ptr(),
+                                      arg_ptrs.as_ptr(), arg_ptrs.len() as _,
+                                      &mut unsaved, 1,
+                                      ffi::CXTranslationUnit_SkipFunctionBodies)
+    });
+    if tu.0.is_null() {
+      return Err(Error::Clang(String::from("libclang could not parse the headers")));
+    }
+    try!(check_diagnostics(&tu));
+
+    let mut decls = Declarations {
+      classes: HashMap::new(),
+      operators: Vec::new(),
+    };
+    decls.collect(unsafe { ffi::clang_getTranslationUnitCursor(tu.0) });
+
+    let mut binder = Binder {
+      allowlist: &self.allowlist,
+      decls: &decls,
+      skipped: Vec::new(),
+    };
+
+    let mut spec = Spec::new();
+    spec.headers = self.headers.clone();
+    for &(ref name, struct_name) in self.allowlist.iter() {
+      let cursor = match decls.find(name) {
+        Some(cursor) => cursor,
+        None         => return Err(Error::Clang(format!("class `{}` not found in the headers", name)))
+      };
+      let path = try!(parse_class_path(leak_str(name)));
+
+      spec.classes.push(ClassSpec {
+        paths:       vec![path],
+        class:       try!(binder.bind_class(name, cursor)),
+        struct_name: struct_name,
+        trait_name:  None,
+        feature:     None,
+        headers:     Vec::new(),
+        defines:     Vec::new(),
+        since:       None,
+      });
+    }
+
+    Ok(Parsed {
+      spec:    spec,
+      skipped: binder.skipped,
+    })
+  }
+}
+
+fn check_diagnostics(tu: &TranslationUnit) -> Result<()> {
+  let mut errors = Vec::new();
+
+  unsafe {
+    for i in 0..ffi::clang_getNumDiagnostics(tu.0) {
+      let diag = ffi::clang_getDiagnostic(tu.0, i);
+      if ffi::clang_getDiagnosticSeverity(diag) >= ffi::CXDiagnostic_Error {
+        let options = ffi::clang_defaultDiagnosticDisplayOptions();
+        errors.push(to_string(ffi::clang_formatDiagnostic(diag, options)));
+      }
+      ffi::clang_disposeDiagnostic(diag);
+    }
+  }
+
+  if errors.len() > 0 {
+    Err(Error::Clang(errors.join("\n")))
+  } else {
+    Ok(())
+  }
+}
+
+/// the class definitions and typedefs of the headers, by qualified
+/// name, and the operators declared outside of classes.
+struct Declarations {
+  classes:   HashMap<String, CXCursor>,
+  operators: Vec<CXCursor>,
+}
+
+impl Declarations {
+  fn collect(&mut self, parent: CXCursor) {
+    for cursor in children(parent).into_iter() {
+      match cursor.kind {
+        ffi::CXCursor_Namespace   |
+        ffi::CXCursor_LinkageSpec => self.collect(cursor),
+        ffi::CXCursor_ClassDecl   |
+        ffi::CXCursor_StructDecl  => {
+          if unsafe { ffi::clang_isCursorDefinition(cursor) } != 0 {
+            self.classes.insert(qualified_name(cursor), cursor);
+            self.collect(cursor);
+          }
+        },
+        ffi::CXCursor_TypedefDecl   |
+        ffi::CXCursor_TypeAliasDecl => {
+          let underlying = unsafe { ffi::clang_getTypedefDeclUnderlyingType(cursor) };
+          if let Some(def) = class_definition(underlying) {
+            self.classes.entry(qualified_name(cursor)).or_insert(def);
+          }
+        },
+        ffi::CXCursor_FunctionDecl if operator_token(&spelling(cursor)).is_some() => {
+          self.operators.push(cursor);
+        },
+        _ => ()
+      }
+    }
+  }
+
+  fn find(&self, name: &str) -> Option<CXCursor> {
+    self.classes.iter()
+      .find(|&(qualified, _)| allowlist_match(name, qualified))
+      .map(|(_, &cursor)| cursor)
+  }
+}
+
+struct Binder<'p> {
+  allowlist: &'p Vec<(String, Option<&'static [u8]>)>,
+  decls:     &'p Declarations,
+  skipped:   Vec<Error>,
+}
+
+impl<'p> Binder<'p> {
+  fn skip(&mut self, cursor: CXCursor, what: &str, reason: &str) {
+    self.skipped.push(Error::Clang(format!("{}: skipped {}: {}", location(cursor), what, reason)));
+  }
+
+  /// the name a binding gives to the declaration qualified as
+  /// `qualified`, see the module documentation.
+  fn bound_name(&self, qualified: &str) -> &'static [u8] {
+    for &(ref entry, _) in self.allowlist.iter() {
+      if allowlist_match(entry, qualified) {
+        return leak_str(entry).as_bytes();
+      }
+    }
+
+    let name = if qualified.starts_with("CryptoPP::") { &qualified[10..] } else { qualified };
+    leak_str(name).as_bytes()
+  }
+
+  fn is_allowed(&self, qualified: &str) -> bool {
+    self.allowlist.iter().any(|&(ref entry, _)| allowlist_match(entry, qualified))
+  }
+
+  fn bind_class(&mut self, name: &str, cursor: CXCursor) -> Result<Class> {
+    let mut cls = class();
+    let owner = qualified_name(cursor);
+    let is_abstract = unsafe { ffi::clang_CXXRecord_isAbstract(cursor) } != 0;
+
+    let mut bases = Vec::new();
+    self.collect_bases(cursor, &mut bases);
+    for base in bases.into_iter() {
+      cls.add_base(try!(parse_class_path(leak_str(&base))));
+    }
+
+    let mut has_ctor = false;
+    let mut ctor_count = 0;
+    let mut method_counts = HashMap::new();
+    let mut op_counts = HashMap::new();
+
+    for child in children(cursor).into_iter() {
+      if child.kind == ffi::CXCursor_Constructor {
+        has_ctor = true;
+      }
+      if !is_public(child) {
+        continue;
+      }
+
+      let member = spelling(child);
+      let what = format!("{}::{}", name, member);
+      match child.kind {
+        ffi::CXCursor_Constructor if is_abstract => (),
+        ffi::CXCursor_Constructor => {
+          match self.function(child, &owner, true) {
+            Ok(f)    => {
+              let ctor_name: &'static [u8] = if f.args.len() == 0 {
+                b""
+              } else if self.is_copy_ctor(&f, name) {
+                b"copy"
+              } else {
+                ctor_count += 1;
+                leak_str(&format!("new{}", ctor_count)).as_bytes()
+              };
+              cls.add_constructor(ctor_name, f.args);
+            },
+            Err(why) => self.skip(child, &format!("constructor of {}", name), &why)
+          }
+        },
+        ffi::CXCursor_CXXMethod => {
+          if let Some(token) = operator_token(&member) {
+            match self.operator(child, &owner, token, false) {
+              Ok((op, f)) => {
+                let alias = overload_alias(&mut op_counts, ::std::str::from_utf8(op.name()).unwrap());
+                cls.add_operator_as(alias, op, false, f);
+              },
+              Err(why)    => self.skip(child, &what, &why)
+            }
+            continue;
+          }
+
+          match self.function(child, &owner, false) {
+            Ok(f)    => {
+              let cpp_name = leak_str(&member).as_bytes();
+              let alias = overload_alias(&mut method_counts, &member);
+              if unsafe { ffi::clang_CXXMethod_isStatic(child) } != 0 {
+                cls.add_static_method_as(alias, cpp_name, f);
+              } else {
+                let is_const = unsafe { ffi::clang_CXXMethod_isConst(child) } != 0;
+                cls.add_method_as(alias, cpp_name, is_const, f);
+              }
+            },
+            Err(why) => self.skip(child, &what, &why)
+          }
+        },
+        ffi::CXCursor_FunctionTemplate    => self.skip(child, &what, "member templates cannot be bound"),
+        ffi::CXCursor_ConversionFunction  => self.skip(child, &what, "conversions cannot be bound"),
+        ffi::CXCursor_EnumDecl            => self.bind_enum(&mut cls, child),
+        ffi::CXCursor_VarDecl             => {
+          match self.map_type(unsafe { ffi::clang_getCursorType(child) }, &owner) {
+            Ok(t) if is_integer(&t) => cls.add_constant(leak_str(&member).as_bytes(), t),
+            _                       => self.skip(child, &what, "only static integer constants can be bound")
+          }
+        },
+        _ => ()
+      }
+    }
+
+    if !has_ctor && !is_abstract {
+      cls.add_constructor(b"", FunctionArgs::new(Vec::new()));
+    }
+
+    for &op_cursor in self.decls.operators.iter() {
+      let first = unsafe { ffi::clang_Cursor_getArgument(op_cursor, 0) };
+      if is_null(first) {
+        continue;
+      }
+      let first_type = unsafe { ffi::clang_getCursorType(first) };
+      let pointee = unsafe { ffi::clang_getPointeeType(first_type) };
+      let decl = class_definition(if pointee.kind == 0 { first_type } else { pointee });
+      if decl.map(qualified_name).as_ref() != Some(&owner) {
+        continue;
+      }
+
+      let member = spelling(op_cursor);
+      let what = format!("{} of {}", member, name);
+      match self.operator(op_cursor, &owner, operator_token(&member).unwrap(), true) {
+        Ok((op, f)) => {
+          let alias = overload_alias(&mut op_counts, ::std::str::from_utf8(op.name()).unwrap());
+          cls.add_operator_as(alias, op, false, f);
+        },
+        Err(why)    => self.skip(op_cursor, &what, &why)
+      }
+    }
+
+    Ok(cls)
+  }
+
+  /// the allowed classes `cursor` derives from publicly, directly or
+  /// not.
+  fn collect_bases(&self, cursor: CXCursor, bases: &mut Vec<String>) {
+    for child in children(cursor).into_iter() {
+      if child.kind != ffi::CXCursor_CXXBaseSpecifier || !is_public(child) {
+        continue;
+      }
+
+      if let Some(base) = class_definition(unsafe { ffi::clang_getCursorType(child) }) {
+        let qualified = qualified_name(base);
+        if self.is_allowed(&qualified) {
+          let name = String::from_utf8_lossy(self.bound_name(&qualified)).into_owned();
+          if !bases.contains(&name) {
+            bases.push(name);
+          }
+        }
+        self.collect_bases(base, bases);
+      }
+    }
+  }
+
+  fn is_copy_ctor(&self, f: &Function, name: &str) -> bool {
+    match f.args.as_slice() {
+      [BasicType::ConstRef(CType::Custom(n))] => *n == name.as_bytes(),
+      _                                       => false
+    }
+  }
+
+  /// a named enum becomes a Rust enum, the values of an anonymous one
+  /// constants, like the ones `CRYPTOPP_CONSTANT` declares.
+  fn bind_enum(&mut self, cls: &mut Class, cursor: CXCursor) {
+    let name = spelling(cursor);
+    let values: Vec<&'static [u8]> = children(cursor).into_iter()
+      .filter(|c| c.kind == ffi::CXCursor_EnumConstantDecl)
+      .map(|c| leak_str(&spelling(c)).as_bytes())
+      .collect();
+
+    if name.len() == 0 || name.starts_with("(anonymous") || name.starts_with("(unnamed") {
+      for value in values.into_iter() {
+        cls.add_constant(value, proto::int());
+      }
+    } else if values.len() > 0 {
+      cls.add_enum(leak_str(&name).as_bytes(), values);
+    }
+  }
+
+  fn function(&mut self, cursor: CXCursor, owner: &str, is_ctor: bool) -> ::std::result::Result<Function, String> {
+    if unsafe { ffi::clang_isFunctionTypeVariadic(ffi::clang_getCursorType(cursor)) } != 0 {
+      return Err(String::from("variadic functions cannot be bound"));
+    }
+
+    let ret = if is_ctor {
+      proto::void()
+    } else {
+      try!(self.map_type(unsafe { ffi::clang_getCursorResultType(cursor) }, owner))
+    };
+
+    let num_args = unsafe { ffi::clang_Cursor_getNumArguments(cursor) };
+    let mut args = Vec::new();
+    for i in 0..(if num_args < 0 { 0 } else { num_args as u32 }) {
+      let arg = unsafe { ffi::clang_Cursor_getArgument(cursor, i) };
+      args.push(try!(self.map_type(unsafe { ffi::clang_getCursorType(arg) }, owner)));
+    }
+
+    Ok(Function { ret: ret, args: FunctionArgs::new(args) })
+  }
+
+  /// a member operator, or a free one when `is_free`, whose first
+  /// argument is then the class itself. compound assignments are bound
+  /// as returning nothing.
+  fn operator(&mut self,
+              cursor: CXCursor,
+              owner: &str,
+              token: &str,
+              is_free: bool) -> ::std::result::Result<(Operator, Function), String> {
+    let mut f = try!(self.function(cursor, owner, false));
+    if is_free {
+      f.args.args.remove(0);
+    }
+
+    let op = try!(Operator::from_cpp(token, f.args.len() > 0).ok_or_else(|| {
+      format!("operator{} has no binding", token)
+    }));
+    if op.is_assign() {
+      f.ret = proto::void();
+    }
+
+    Ok((op, f))
+  }
+
+  /// the binding of the type `t` used by a member of the class
+  /// qualified as `owner`.
+  fn map_type(&self, t: CXType, owner: &str) -> ::std::result::Result<BasicType, String> {
+    match t.kind {
+      ffi::CXType_Pointer         |
+      ffi::CXType_LValueReference => {
+        let pointee = unsafe { ffi::clang_getPointeeType(t) };
+        let is_const = unsafe { ffi::clang_isConstQualifiedType(pointee) } != 0;
+        let ctype = try!(self.map_ctype(pointee, owner));
+        let btype = match (t.kind == ffi::CXType_Pointer, is_const) {
+          (true, true)   => BasicType::ConstPointer(ctype),
+          (true, false)  => BasicType::MutPointer(ctype),
+          (false, true)  => BasicType::ConstRef(ctype),
+          (false, false) => BasicType::MutRef(ctype)
+        };
+
+        match btype {
+          BasicType::ConstRef(_) => Ok(btype),
+          _ if is_string_ctype(&btype) => Err(format!("`{}` is a std::string other than by value \
+                                                      or const reference", type_spelling(t))),
+          _                      => Ok(btype)
+        }
+      },
+      ffi::CXType_RValueReference => Err(format!("rvalue reference `{}`", type_spelling(t))),
+      _                           => match try!(self.map_ctype(t, owner)) {
+        ctype @ CType::Custom(_) => Ok(BasicType::Owned(ctype)),
+        ctype                    => Ok(BasicType::Simple(ctype))
+      }
+    }
+  }
+
+  fn map_ctype(&self, t: CXType, owner: &str) -> ::std::result::Result<CType, String> {
+    let unsupported = || Err(format!("unsupported type `{}`", type_spelling(t)));
+
+    match t.kind {
+      ffi::CXType_Elaborated => self.map_ctype(unsafe { ffi::clang_Type_getNamedType(t) }, owner),
+      ffi::CXType_Typedef    => {
+        let decl = unsafe { ffi::clang_getTypeDeclaration(t) };
+        let qualified = qualified_name(decl);
+        if qualified == "std::string" || qualified.ends_with("::std::string") {
+          return Ok(CType::StdString);
+        }
+        match primitive(&spelling(decl)) {
+          Some(CType::StdString)  |
+          Some(CType::Utf8String) |
+          None                    => self.map_ctype(unsafe { ffi::clang_getCanonicalType(t) }, owner),
+          Some(ctype)             => Ok(ctype)
+        }
+      },
+      ffi::CXType_Void       => Ok(CType::Void),
+      ffi::CXType_Bool       => Ok(CType::Bool),
+      ffi::CXType_Char_S     |
+      ffi::CXType_Char_U     => Ok(CType::Char),
+      ffi::CXType_UChar      => Ok(CType::UChar),
+      ffi::CXType_Int        => Ok(CType::Int),
+      ffi::CXType_UInt       => Ok(CType::UInt),
+      ffi::CXType_Long       => Ok(CType::Long),
+      ffi::CXType_LongLong   => Ok(CType::LongLong),
+      ffi::CXType_ULongLong  => Ok(CType::Word64),
+      ffi::CXType_Double     => Ok(CType::Double),
+      ffi::CXType_Enum       => {
+        let decl = unsafe { ffi::clang_getTypeDeclaration(t) };
+        let parent = qualified_name(unsafe { ffi::clang_getCursorSemanticParent(decl) });
+        if parent != owner {
+          return Err(format!("enum `{}` is not declared by the class", type_spelling(t)));
+        }
+        Ok(CType::Enum(self.bound_name(&qualified_name(decl))))
+      },
+      ffi::CXType_Record     => {
+        let decl = unsafe { ffi::clang_getTypeDeclaration(t) };
+        let qualified = qualified_name(decl);
+        if qualified.starts_with("std::") && qualified.ends_with("::basic_string") ||
+           qualified == "std::basic_string" {
+          let canonical = type_spelling(unsafe { ffi::clang_getCanonicalType(t) });
+          return if canonical.contains("basic_string<char") { Ok(CType::StdString) } else { unsupported() };
+        }
+        if unsafe { ffi::clang_Type_getNumTemplateArguments(t) } > 0 {
+          return Err(format!("template class `{}`", type_spelling(t)));
+        }
+        Ok(CType::Custom(self.bound_name(&qualified)))
+      },
+      _                      => unsupported()
+    }
+  }
+}
+
+fn is_integer(t: &BasicType) -> bool {
+  let t = match t {
+    &BasicType::Simple(ref t) => t,
+    _                         => return false
+  };
+
+  match t {
+    &CType::Char     |
+    &CType::UChar    |
+    &CType::Byte     |
+    &CType::Int      |
+    &CType::UInt     |
+    &CType::Long     |
+    &CType::LongLong |
+    &CType::SizeT    |
+    &CType::Word     |
+    &CType::Word32   |
+    &CType::Word64   |
+    &CType::LWord    => true,
+    _                => false
+  }
+}
+
+fn is_string_ctype(t: &BasicType) -> bool {
+  match t {
+    &BasicType::MutPointer(CType::StdString)   |
+    &BasicType::ConstPointer(CType::StdString) |
+    &BasicType::MutRef(CType::StdString)       => true,
+    _                                          => false
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::collections::HashMap;
+  use super::{allowlist_match, operator_token, overload_alias};
+
+  #[test]
+  fn names() {
+    assert!(allowlist_match("SHA256", "CryptoPP::SHA256"));
+    assert!(allowlist_match("CryptoPP::SHA256", "CryptoPP::SHA256"));
+    assert!(!allowlist_match("SHA256", "CryptoPP::Weak::SHA256"));
+
+    assert_eq!(operator_token("operator+="), Some("+="));
+    assert_eq!(operator_token("operator <<"), Some("<<"));
+    assert_eq!(operator_token("operator bool"), None);
+    assert_eq!(operator_token("operators"), None);
+
+    let mut counts = HashMap::new();
+    assert_eq!(overload_alias(&mut counts, "Encode"), b"Encode");
+    assert_eq!(overload_alias(&mut counts, "Encode"), b"Encode_2");
+  }
+}
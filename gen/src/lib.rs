@@ -1,4 +1,3 @@
-use std::collections::hash_map::HashMap;
 use std::io;
 use std::io::Write;
 use std::convert::From;
@@ -6,10 +5,22 @@ use std::error;
 use std::fs::File;
 use std::borrow::Borrow;
 
+extern crate toml;
+
+pub mod spec;
+pub mod probe;
+#[cfg(feature = "clang")]
+pub mod clang;
+
 #[derive(Debug)]
 pub enum Error {
   IO(io::Error),
-  Unexpected(String)
+  Unexpected(String),
+  /// a binding spec file could not be parsed.
+  Spec(String),
+  /// libclang could not parse the headers, or a declaration could not
+  /// be bound, see `clang::HeaderParser`.
+  Clang(String)
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -27,6 +38,23 @@ impl From<std::string::FromUtf8Error> for Error {
   }
 }
 
+/// status codes a shim stores in its `rcpp_exception` out parameter.
+/// the first group mirrors `CryptoPP::Exception::ErrorType`.
+const EXCEPTION_CODES: &'static [(&'static [u8], u32)] = &[
+  (b"NONE",                        0),
+  (b"NOT_IMPLEMENTED",             1),
+  (b"INVALID_ARGUMENT",            2),
+  (b"CANNOT_FLUSH",                3),
+  (b"DATA_INTEGRITY_CHECK_FAILED", 4),
+  (b"INVALID_DATA_FORMAT",         5),
+  (b"IO_ERROR",                    6),
+  (b"OTHER_ERROR",                 7),
+  (b"BAD_ALLOC",                   8),
+  (b"STD_EXCEPTION",               9),
+  (b"UNKNOWN",                     10),
+  (b"RUST_PANIC",                  11),
+];
+
 pub fn generate_prelude<T: Write>(mut stream: T) -> Result<()> {
   try!(stream.write_all(b"
     pub trait CPPContext {
@@ -35,113 +63,493 @@ pub fn generate_prelude<T: Write>(mut stream: T) -> Result<()> {
         self.mut_ctx()
       }
     }
-  \n\n"));
+
+    /// implemented by wrappers of classes derived from the bound class
+    /// `B`, gives the address of their `B` subobject.
+    pub trait Upcast<B> : CPPContext {
+      fn mut_base_ctx(&self) -> *mut c_void;
+      fn base_ctx(&self) -> *const c_void {
+        self.mut_base_ctx()
+      }
+    }
+
+    /// out parameter of every shim. `code` is `EXC_NONE` unless the
+    /// shim caught a C++ exception, in which case `what` may hold a
+    /// malloc'd copy of the exception message.
+    #[repr(C)]
+    pub struct Exception {
+      pub code: c_int,
+      pub what: *mut c_char
+    }
+
+    impl Drop for Exception {
+      fn drop(&mut self) {
+        if !self.what.is_null() {
+          unsafe { ::libc::free(self.what as *mut c_void) };
+        }
+      }
+    }
+
+    impl Exception {
+      /// an exception holding a malloc'd copy of `what`, for handing
+      /// over to C++.
+      pub fn new(code: c_int, what: &str) -> Exception {
+        let copy = unsafe { ::libc::malloc((what.len() + 1) as size_t) as *mut c_char };
+        if !copy.is_null() {
+          unsafe {
+            ::std::ptr::copy_nonoverlapping(what.as_ptr() as *const c_char, copy, what.len());
+            *copy.offset(what.len() as isize) = 0;
+          }
+        }
+
+        Exception { code: code, what: copy }
+      }
+    }
+
+    /// calls a shim with a fresh exception slot, handing the slot
+    /// back as an error if the shim caught something.
+    pub fn catch<T, F>(f: F) -> ::std::result::Result<T, Exception>
+        where F: FnOnce(*mut Exception) -> T {
+      let mut exc = Exception { code: EXC_NONE, what: 0 as *mut c_char };
+      let val = f(&mut exc);
+
+      if exc.code == EXC_NONE {
+        Ok(val)
+      } else {
+        Err(exc)
+      }
+    }
+
+    /// runs the Rust side of a call made by a C++ trampoline. an error
+    /// or a panic is stored in `exc` for the trampoline to rethrow.
+    pub fn callback<T, E, F>(exc: *mut Exception, f: F) -> T
+        where T: Default,
+              E: Into<Exception>,
+              F: FnOnce() -> ::std::result::Result<T, E> {
+      let err = match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(f)) {
+        Ok(Ok(val))  => return val,
+        Ok(Err(err)) => err.into(),
+        Err(payload) => {
+          let what = if let Some(s) = payload.downcast_ref::<&str>() {
+            *s
+          } else if let Some(s) = payload.downcast_ref::<String>() {
+            &s[..]
+          } else {
+            \"\"
+          };
+
+          Exception::new(EXC_RUST_PANIC, what)
+        }
+      };
+
+      unsafe { ::std::ptr::write(exc, err) };
+      T::default()
+    }
+
+    /// `len` bytes at `ptr`, which may be null if `len` is 0.
+    pub unsafe fn slice<'a>(ptr: *const u8, len: size_t) -> &'a [u8] {
+      if len == 0 {
+        &[]
+      } else {
+        ::std::slice::from_raw_parts(ptr, len as usize)
+      }
+    }
+
+    pub unsafe fn slice_mut<'a>(ptr: *mut u8, len: size_t) -> &'a mut [u8] {
+      if len == 0 {
+        &mut []
+      } else {
+        ::std::slice::from_raw_parts_mut(ptr, len as usize)
+      }
+    }
+
+    /// a `std::string` passed to or returned by a shim. an argument
+    /// borrows its bytes, those of a returned string are malloc'd and
+    /// taken over by `into_vec` or `into_string`.
+    #[repr(C)]
+    pub struct CppString {
+      pub data: *mut c_char,
+      pub len: size_t
+    }
+
+    impl CppString {
+      pub fn borrow(bytes: &[u8]) -> CppString {
+        CppString { data: bytes.as_ptr() as *mut c_char, len: bytes.len() as size_t }
+      }
+
+      pub unsafe fn into_vec(self) -> Vec<u8> {
+        let vec = slice(self.data as *const u8, self.len).to_vec();
+        ::libc::free(self.data as *mut c_void);
+        vec
+      }
+
+      /// invalid UTF-8 is replaced with U+FFFD.
+      pub unsafe fn into_string(self) -> String {
+        match String::from_utf8(self.into_vec()) {
+          Ok(s)  => s,
+          Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned()
+        }
+      }
+    }
+  \n"));
+
+  for &(name, code) in EXCEPTION_CODES.iter() {
+    try!(stream.write_all(b"pub const EXC_"));
+    try!(stream.write_all(name));
+    try!(write!(stream, ": c_int = {};\n", code));
+  }
+  try!(stream.write_all(b"\n"));
+
+  Ok(())
+}
+
+/// writes the C++ support code the generated shims rely on. must be
+/// written after `cryptopp/cryptlib.h` has been included.
+pub fn generate_cpp_prelude<T: Write>(mut stream: T) -> Result<()> {
+  try!(stream.write_all(b"#include <cstdlib>\n"));
+  try!(stream.write_all(b"#include <cstring>\n"));
+  try!(stream.write_all(b"#include <exception>\n"));
+  try!(stream.write_all(b"#include <new>\n"));
+  try!(stream.write_all(b"#include <stdexcept>\n"));
+  try!(stream.write_all(b"#include <string>\n\n"));
+
+  try!(stream.write_all(b"enum {\n"));
+  for &(name, code) in EXCEPTION_CODES.iter() {
+    try!(stream.write_all(b"  RCPP_EXC_"));
+    try!(stream.write_all(name));
+    try!(write!(stream, " = {},\n", code));
+  }
+  try!(stream.write_all(b"};\n\n"));
+
+  try!(stream.write_all(b"\
+// `word` is bound as `size_t` on the rust side.
+typedef char rcpp_word_is_size_t[sizeof(CryptoPP::word) == sizeof(size_t) ? 1 : -1];
+
+struct rcpp_exception {
+  int code;
+  char* what;
+};
+
+// a std::string crossing a shim. an argument borrows the bytes of the
+// caller, a returned string is malloc'd and released by the caller.
+struct rcpp_string {
+  char* data;
+  size_t len;
+};
+
+template <typename T>
+static T rcpp_default() {
+  return T();
+}
+
+static std::string rcpp_from_string(rcpp_string s) {
+  return s.len > 0 ? std::string(s.data, s.len) : std::string();
+}
+
+static rcpp_string rcpp_to_string(const std::string& s) {
+  rcpp_string copy = { NULL, s.size() };
+  if (copy.len > 0) {
+    copy.data = static_cast<char*>(std::malloc(copy.len));
+    if (copy.data == NULL) {
+      throw std::bad_alloc();
+    }
+    std::memcpy(copy.data, s.data(), copy.len);
+  }
+  return copy;
+}
+
+static char* rcpp_copy_what(const char* what) {
+  size_t len = std::strlen(what);
+  char* copy = static_cast<char*>(std::malloc(len + 1));
+  if (copy != NULL) {
+    std::memcpy(copy, what, len + 1);
+  }
+  return copy;
+}
+
+static int rcpp_error_type_code(CryptoPP::Exception::ErrorType t) {
+  switch (t) {
+    case CryptoPP::Exception::NOT_IMPLEMENTED:
+      return RCPP_EXC_NOT_IMPLEMENTED;
+    case CryptoPP::Exception::INVALID_ARGUMENT:
+      return RCPP_EXC_INVALID_ARGUMENT;
+    case CryptoPP::Exception::CANNOT_FLUSH:
+      return RCPP_EXC_CANNOT_FLUSH;
+    case CryptoPP::Exception::DATA_INTEGRITY_CHECK_FAILED:
+      return RCPP_EXC_DATA_INTEGRITY_CHECK_FAILED;
+    case CryptoPP::Exception::INVALID_DATA_FORMAT:
+      return RCPP_EXC_INVALID_DATA_FORMAT;
+    case CryptoPP::Exception::IO_ERROR:
+      return RCPP_EXC_IO_ERROR;
+    default:
+      return RCPP_EXC_OTHER_ERROR;
+  }
+}
+
+// thrown by a trampoline when the rust code it called panicked.
+class rcpp_rust_panic : public std::runtime_error {
+public:
+  explicit rcpp_rust_panic(const std::string& what) : std::runtime_error(what) {}
+};
+
+// must only be called from inside a catch block.
+static void rcpp_catch(rcpp_exception* exc) {
+  try {
+    throw;
+  } catch (const rcpp_rust_panic& e) {
+    exc->code = RCPP_EXC_RUST_PANIC;
+    exc->what = rcpp_copy_what(e.what());
+  } catch (const CryptoPP::Exception& e) {
+    exc->code = rcpp_error_type_code(e.GetErrorType());
+    exc->what = rcpp_copy_what(e.what());
+  } catch (const std::bad_alloc&) {
+    exc->code = RCPP_EXC_BAD_ALLOC;
+  } catch (const std::exception& e) {
+    exc->code = RCPP_EXC_STD_EXCEPTION;
+    exc->what = rcpp_copy_what(e.what());
+  } catch (...) {
+    exc->code = RCPP_EXC_UNKNOWN;
+  }
+}
+
+// throws the error a rust callback stored in `exc`, if any. the inverse
+// of `rcpp_catch`.
+static void rcpp_rethrow(rcpp_exception* exc) {
+  if (exc->code == RCPP_EXC_NONE) {
+    return;
+  }
+
+  std::string what(exc->what != NULL ? exc->what : \"\");
+  std::free(exc->what);
+  exc->what = NULL;
+
+  switch (exc->code) {
+    case RCPP_EXC_NOT_IMPLEMENTED:
+      throw CryptoPP::Exception(CryptoPP::Exception::NOT_IMPLEMENTED, what);
+    case RCPP_EXC_INVALID_ARGUMENT:
+      throw CryptoPP::Exception(CryptoPP::Exception::INVALID_ARGUMENT, what);
+    case RCPP_EXC_CANNOT_FLUSH:
+      throw CryptoPP::Exception(CryptoPP::Exception::CANNOT_FLUSH, what);
+    case RCPP_EXC_DATA_INTEGRITY_CHECK_FAILED:
+      throw CryptoPP::Exception(CryptoPP::Exception::DATA_INTEGRITY_CHECK_FAILED, what);
+    case RCPP_EXC_INVALID_DATA_FORMAT:
+      throw CryptoPP::Exception(CryptoPP::Exception::INVALID_DATA_FORMAT, what);
+    case RCPP_EXC_IO_ERROR:
+      throw CryptoPP::Exception(CryptoPP::Exception::IO_ERROR, what);
+    case RCPP_EXC_OTHER_ERROR:
+      throw CryptoPP::Exception(CryptoPP::Exception::OTHER_ERROR, what);
+    case RCPP_EXC_BAD_ALLOC:
+      throw std::bad_alloc();
+    case RCPP_EXC_RUST_PANIC:
+      throw rcpp_rust_panic(what);
+    default:
+      throw std::runtime_error(what);
+  }
+}
+
+"));
+
+  Ok(())
+}
+
+fn generate_cpp_try_open(out_stream: &mut Write) -> Result<()> {
+  try!(out_stream.write_all(b" {\n  try {\n    "));
+
+  Ok(())
+}
+
+/// closes the body opened by `generate_cpp_try_open`. on an exception
+/// the shim returns `default_ret`, or nothing if it is empty.
+fn generate_cpp_try_close(default_ret: &[u8],
+                          out_stream: &mut Write) -> Result<()> {
+  try!(out_stream.write_all(b"\n  } catch (...) {\n"));
+  try!(out_stream.write_all(b"    rcpp_catch(exc);\n"));
+  if default_ret.len() > 0 {
+    try!(out_stream.write_all(b"    return "));
+    try!(out_stream.write_all(default_ret));
+    try!(out_stream.write_all(b";\n"));
+  }
+  try!(out_stream.write_all(b"  }\n}"));
+
+  Ok(())
+}
+
+/// opens the C header declaring the shims, see `Context::with_c_header`.
+pub fn generate_c_header_prelude<T: Write>(mut stream: T) -> Result<()> {
+  try!(stream.write_all(b"\
+#ifndef RUSTCRYPTOPP_H
+#define RUSTCRYPTOPP_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+"));
+
+  try!(stream.write_all(b"enum {\n"));
+  for &(name, code) in EXCEPTION_CODES.iter() {
+    try!(stream.write_all(b"  RCPP_EXC_"));
+    try!(stream.write_all(name));
+    try!(write!(stream, " = {},\n", code));
+  }
+  try!(stream.write_all(b"};\n\n"));
+
+  try!(stream.write_all(b"\
+/* set by a shim when the call throws. `what` is allocated with
+   `malloc` and must be released by the caller. */
+typedef struct rcpp_exception {
+  int code;
+  char* what;
+} rcpp_exception;
+
+/* a std::string. an argument only borrows `data`, the `data` of a
+   returned string is allocated with `malloc` and must be released by
+   the caller. it is not NUL terminated. */
+typedef struct rcpp_string {
+  char* data;
+  size_t len;
+} rcpp_string;
+
+#ifdef __cplusplus
+extern \"C\" {
+#endif
+
+"));
+
+  Ok(())
+}
+
+/// closes the header opened by `generate_c_header_prelude`.
+pub fn generate_c_header_end<T: Write>(mut stream: T) -> Result<()> {
+  try!(stream.write_all(b"\
+#ifdef __cplusplus
+}
+#endif
+
+#endif
+"));
 
   Ok(())
 }
 
-pub struct Context<T, U> {
+/// the streams bindings are generated into. the C header is thrown
+/// away unless a stream is given for it with `with_c_header`.
+pub struct Context<T, U, V = io::Sink> {
   pub cpp_stream: T,
-  pub rs_binding_stream: U
+  pub rs_binding_stream: U,
+  pub c_header_stream: V
 }
 
 impl<T: Write, U: Write> Context<T, U> {
   pub fn new(cpp_stream: T,
              rs_binding_stream: U) -> Context<T, U> {
+    Context::with_c_header(cpp_stream, rs_binding_stream, io::sink())
+  }
+}
+
+impl<T: Write, U: Write, V: Write> Context<T, U, V> {
+  pub fn with_c_header(cpp_stream: T,
+                       rs_binding_stream: U,
+                       c_header_stream: V) -> Context<T, U, V> {
     Context {
       cpp_stream: cpp_stream,
-      rs_binding_stream: rs_binding_stream
+      rs_binding_stream: rs_binding_stream,
+      c_header_stream: c_header_stream
     }
   }
 }
 
-pub enum FunctionArgs {
-  None,
-  Args1([proto::BasicType; 1]),
-  Args2([proto::BasicType; 2]),
-  Args3([proto::BasicType; 3]),
+/// the argument list of a method or constructor.
+pub struct FunctionArgs {
+  args: Vec<proto::BasicType>
 }
 
 impl FunctionArgs {
-  pub fn as_slice(&self) -> Option<&[proto::BasicType]> {
-    use self::FunctionArgs::*;
-
-    macro_rules! args_arm {
-      ($arr:expr) => (
-        Some(&$arr[..])
-      )
+  pub fn new(args: Vec<proto::BasicType>) -> FunctionArgs {
+    FunctionArgs {
+      args: args
     }
+  }
 
-    match self {
-      &None           => Option::None,
-      &Args1(ref arr) => args_arm!(arr),
-      &Args2(ref arr) => args_arm!(arr),
-      &Args3(ref arr) => args_arm!(arr),
-    }
+  pub fn as_slice(&self) -> &[proto::BasicType] {
+    &self.args[..]
   }
 
   pub fn len(&self) -> usize {
-    match self.as_slice() {
-      Some(ref slice) => slice.len(),
-      None            => 0
-    }
+    self.args.len()
   }
 
   pub fn generate_proto_cpp(&self, out_stream: &mut Write) -> Result<()> {
-    if let Some(slice) = self.as_slice() {
-      let mut i = 0u32;
+    let mut i = 0u32;
 
-      for btype in slice.iter() {
-        if i > 0 {
-          try!(out_stream.write_all(b", "));
-        }
+    for btype in self.args.iter() {
+      if i > 0 {
+        try!(out_stream.write_all(b", "));
+      }
 
-        try!(btype.generate_cpp(out_stream));
+      try!(btype.generate_cpp(out_stream));
 
-        try!(write!(out_stream, " arg{}", i));
-        i += 1;
-      }
+      try!(write!(out_stream, " arg{}", i));
+      i += 1;
     }
 
     Ok(())
   }
 
   pub fn generate_apply_cpp(&self, out_stream: &mut Write) -> Result<()> {
-    if let Some(slice) = self.as_slice() {
-      let mut i = 0u32;
-
-      for btype in slice.iter() {
-        if i > 0 {
-          try!(out_stream.write_all(b", "));
-        }
+    let mut i = 0u32;
 
-        try!(out_stream.write_all(b" "));
-        if btype.is_ref() {
-          try!(out_stream.write_all(b"*"));
-        }
+    for btype in self.args.iter() {
+      if i > 0 {
+        try!(out_stream.write_all(b", "));
+      }
 
-        try!(write!(out_stream, "arg{}", i));
+      try!(out_stream.write_all(b" "));
+      if btype.is_string() {
+        try!(write!(out_stream, "rcpp_from_string(arg{})", i));
         i += 1;
+        continue;
+      }
+      if btype.is_ref() || btype.is_owned() {
+        try!(out_stream.write_all(b"*"));
       }
+
+      try!(write!(out_stream, "arg{}", i));
+      i += 1;
     }
 
     Ok(())
   }
 
-  pub fn generate_proto_rs(&self, out_stream: &mut Write) -> Result<()> {
-    if let Some(slice) = self.as_slice() {
-      let mut i = 0u32;
+  pub fn generate_proto_c(&self, out_stream: &mut Write) -> Result<()> {
+    let mut i = 0u32;
 
-      for btype in slice.iter() {
-        if i > 0 {
-          try!(out_stream.write_all(b", "));
-        }
+    for btype in self.args.iter() {
+      if i > 0 {
+        try!(out_stream.write_all(b", "));
+      }
 
-        try!(write!(out_stream, "arg{}: ", i));
-        try!(btype.generate_rs(out_stream));
+      try!(generate_c_type(btype, out_stream));
 
-        i += 1;
+      try!(write!(out_stream, " arg{}", i));
+      i += 1;
+    }
+
+    Ok(())
+  }
+
+  pub fn generate_proto_rs(&self, out_stream: &mut Write) -> Result<()> {
+    let mut i = 0u32;
+
+    for btype in self.args.iter() {
+      if i > 0 {
+        try!(out_stream.write_all(b", "));
       }
+
+      try!(write!(out_stream, "arg{}: ", i));
+      try!(btype.generate_rs(out_stream));
+
+      i += 1;
     }
 
     Ok(())
@@ -153,20 +561,190 @@ pub struct Function {
   pub args: FunctionArgs
 }
 
+/// a bound method, static method or free function. `cpp_name` is the
+/// C++ name that gets called, which differs from the name the binding
+/// is exported under when an overload is given an alias. `is_const`
+/// only matters for instance methods.
 pub struct Method {
   func: Function,
-  is_const: bool
+  is_const: bool,
+  cpp_name: &'static [u8]
 }
 
-pub fn method(func: Function, is_const: bool) -> Method {
+pub fn method(cpp_name: &'static [u8], func: Function, is_const: bool) -> Method {
   Method {
     func: func,
-    is_const: is_const
+    is_const: is_const,
+    cpp_name: cpp_name
+  }
+}
+
+/// a C++ operator with the object a binding is called on as its left
+/// hand side. `Neg` takes no argument, the others take the right hand
+/// side.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Operator {
+  Add,
+  Sub,
+  Mul,
+  Div,
+  Rem,
+  Shl,
+  Shr,
+  Neg,
+  Eq,
+  Lt,
+  AddAssign,
+  SubAssign,
+  MulAssign,
+  DivAssign,
+  RemAssign,
+  ShlAssign,
+  ShrAssign
+}
+
+impl Operator {
+  /// `-` is `Sub` with a right hand side and `Neg` without one.
+  pub fn from_cpp(token: &str, has_rhs: bool) -> Option<Operator> {
+    use self::Operator::*;
+
+    Some(match (token, has_rhs) {
+      ("+", true)   => Add,
+      ("-", true)   => Sub,
+      ("*", true)   => Mul,
+      ("/", true)   => Div,
+      ("%", true)   => Rem,
+      ("<<", true)  => Shl,
+      (">>", true)  => Shr,
+      ("-", false)  => Neg,
+      ("==", true)  => Eq,
+      ("<", true)   => Lt,
+      ("+=", true)  => AddAssign,
+      ("-=", true)  => SubAssign,
+      ("*=", true)  => MulAssign,
+      ("/=", true)  => DivAssign,
+      ("%=", true)  => RemAssign,
+      ("<<=", true) => ShlAssign,
+      (">>=", true) => ShrAssign,
+      _             => return None
+    })
+  }
+
+  pub fn cpp_token(&self) -> &'static [u8] {
+    use self::Operator::*;
+
+    match *self {
+      Add       => b"+",
+      Sub       |
+      Neg       => b"-",
+      Mul       => b"*",
+      Div       => b"/",
+      Rem       => b"%",
+      Shl       => b"<<",
+      Shr       => b">>",
+      Eq        => b"==",
+      Lt        => b"<",
+      AddAssign => b"+=",
+      SubAssign => b"-=",
+      MulAssign => b"*=",
+      DivAssign => b"/=",
+      RemAssign => b"%=",
+      ShlAssign => b"<<=",
+      ShrAssign => b">>=",
+    }
+  }
+
+  /// the default name of the binding, which is also the name of the
+  /// method of the matching Rust trait, `partial_cmp` aside.
+  pub fn name(&self) -> &'static [u8] {
+    use self::Operator::*;
+
+    match *self {
+      Add       => b"add",
+      Sub       => b"sub",
+      Mul       => b"mul",
+      Div       => b"div",
+      Rem       => b"rem",
+      Shl       => b"shl",
+      Shr       => b"shr",
+      Neg       => b"neg",
+      Eq        => b"eq",
+      Lt        => b"lt",
+      AddAssign => b"add_assign",
+      SubAssign => b"sub_assign",
+      MulAssign => b"mul_assign",
+      DivAssign => b"div_assign",
+      RemAssign => b"rem_assign",
+      ShlAssign => b"shl_assign",
+      ShrAssign => b"shr_assign",
+    }
+  }
+
+  fn rs_trait(&self) -> &'static [u8] {
+    use self::Operator::*;
+
+    match *self {
+      Add       => b"std::ops::Add",
+      Sub       => b"std::ops::Sub",
+      Mul       => b"std::ops::Mul",
+      Div       => b"std::ops::Div",
+      Rem       => b"std::ops::Rem",
+      Shl       => b"std::ops::Shl",
+      Shr       => b"std::ops::Shr",
+      Neg       => b"std::ops::Neg",
+      Eq        => b"std::cmp::PartialEq",
+      Lt        => b"std::cmp::PartialOrd",
+      AddAssign => b"std::ops::AddAssign",
+      SubAssign => b"std::ops::SubAssign",
+      MulAssign => b"std::ops::MulAssign",
+      DivAssign => b"std::ops::DivAssign",
+      RemAssign => b"std::ops::RemAssign",
+      ShlAssign => b"std::ops::ShlAssign",
+      ShrAssign => b"std::ops::ShrAssign",
+    }
+  }
+
+  /// compound assignments modify the object and return nothing.
+  pub fn is_assign(&self) -> bool {
+    use self::Operator::*;
+
+    match *self {
+      AddAssign | SubAssign | MulAssign | DivAssign |
+      RemAssign | ShlAssign | ShrAssign => true,
+      _                                 => false
+    }
+  }
+
+  fn is_comparison(&self) -> bool {
+    *self == Operator::Eq || *self == Operator::Lt
   }
 }
 
+/// a bound operator. `with_trait` asks `NamedClass::write_struct` to
+/// implement the matching `std::ops` or `std::cmp` trait as well.
+pub struct OperatorMethod {
+  op: Operator,
+  func: Function,
+  with_trait: bool
+}
+
 #[macro_export]
 macro_rules! class {
+  ( path $path:expr => $b:tt ) => ({
+    let anon_class = class_bindings_block!($crate::class() => $b);
+    $crate::NamedClass::with_path(
+      $path,
+      anon_class
+    )
+  });
+
+  ( path $path:expr , $anon_class:expr ) => ({
+    $crate::NamedClass::with_path(
+      $path,
+      $anon_class
+    )
+  });
+
   ( $name:expr => $b:tt ) => ({
     let anon_class = class_bindings_block!($crate::class() => $b);
     $crate::NamedClass::new(
@@ -211,8 +789,7 @@ macro_rules! prototype_class {
 }
 
 pub struct NamedClass<'a, T> {
-  pub namespace:  Vec<&'a [u8]>,
-  pub name:       &'a [u8],
+  pub path:       ClassPath<'a>,
   pub anon_class: T,
 }
 
@@ -220,16 +797,20 @@ impl<'a, T: Borrow<Class>> NamedClass<'a, T> {
   pub fn new(namespace:  Vec<&'a [u8]>,
              name:       &'a [u8],
              anon_class: T) -> NamedClass<'a, T> {
+    NamedClass::with_path(class_path(namespace, name), anon_class)
+  }
+
+  pub fn with_path(path:       ClassPath<'a>,
+                   anon_class: T) -> NamedClass<'a, T> {
     NamedClass {
-      namespace: namespace,
-      name: name,
+      path: path,
       anon_class: anon_class,
     }
   }
 
   pub fn c_path(&self) -> Result<String> {
     let mut c_path: Vec<u8> = Vec::new();
-    try!(generate_c_path(&self.namespace, self.name, &mut c_path));
+    try!(self.path.generate_c_path(&mut c_path));
     let s = try!(String::from_utf8(c_path));
 
     Ok(s)
@@ -250,7 +831,20 @@ impl<'a, T: Borrow<Class>> NamedClass<'a, T> {
     self.write_struct(name, &mut stream)
   }
 
+  /// writes a Rust struct owning an instance of the class, with safe
+  /// wrappers for its constructors, methods and static methods. each
+  /// `(const byte*, size_t)` or `(byte*, size_t)` argument pair is
+  /// taken as a `&[u8]` or `&mut [u8]` and a reference to a bound class
+  /// as an `Upcast` trait object. functions with arguments or returns
+  /// that have no safe form are only reachable through `cpp`.
+  ///
+  /// the constructor named `""` becomes `new` and gives `Default`, a
+  /// constructor taking a `const` reference to the class gives `Clone`.
   pub fn write_struct<U: Write>(&self, name: &'a [u8], mut stream: U) -> Result<()> {
+    let cls = self.anon_class.borrow();
+    let mut c_path = Vec::new();
+    try!(self.path.generate_c_path(&mut c_path));
+
     try!(stream.write_all(b"use std;\n\n"));
 
     try!(stream.write_all(b"pub struct "));
@@ -261,86 +855,1064 @@ impl<'a, T: Borrow<Class>> NamedClass<'a, T> {
     try!(stream.write_all(name));
     try!(stream.write_all(b" {\n  fn drop(&mut self) {\n"));
     try!(stream.write_all(b"    unsafe { cpp::del_"));
-    try!(generate_c_path(&self.namespace, self.name, &mut stream));
+    try!(stream.write_all(&c_path));
     try!(stream.write_all(b"(self.ctx) };\n  }\n}\n"));
 
-    try!(stream.write_all(b"impl "));
-    try!(stream.write_all(name));
-    try!(stream.write_all(b" {\n  pub fn new() -> "));
-    try!(stream.write_all(name));
-    try!(stream.write_all(b" {\n    let ctx = unsafe { cpp::new_"));
-    try!(generate_c_path(&self.namespace, self.name, &mut stream));
-    try!(stream.write_all(b"() };\n\n    "));
-    try!(stream.write_all(name));
-    try!(stream.write_all(b" { ctx: ctx }\n  }\n}\n\n"));
+    let mut body = Vec::new();
+    let mut copy_ctor = None;
+    let mut has_default = false;
+    for &(ctor_name, ref ctor_args) in cls.ctors.iter() {
+      let args = match safe_args(ctor_args.as_slice()) {
+        Some(args) => args,
+        None       => continue
+      };
+
+      if ctor_name.len() < 1 {
+        has_default = true;
+      }
+      if is_copy_ctor(&args, &c_path) {
+        copy_ctor = Some(ctor_name);
+      }
+
+      try!(write_safe_ctor(name, &c_path, ctor_name, &args, &mut body));
+    }
+
+    for &(method_name, ref method_desc) in cls.methods.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"mth_"));
+      try!(shim_name.write_all(&c_path));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(method_name));
+
+      try!(write_safe_fn(name, &c_path, method_name, Some(method_desc.is_const),
+                         &shim_name, &method_desc.func, &mut body));
+    }
+
+    for &(op_name, ref op_desc) in cls.operators.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"op_"));
+      try!(shim_name.write_all(&c_path));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(op_name));
+
+      let mut fn_name = Vec::new();
+      try!(fn_name.write_all(b"try_"));
+      try!(fn_name.write_all(op_name));
+
+      try!(write_safe_fn(name, &c_path, &fn_name, Some(!op_desc.op.is_assign()),
+                         &shim_name, &op_desc.func, &mut body));
+    }
 
+    for &(method_name, ref method_desc) in cls.static_methods.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"smth_"));
+      try!(shim_name.write_all(&c_path));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(method_name));
+
+      try!(write_safe_fn(name, &c_path, method_name, None,
+                         &shim_name, &method_desc.func, &mut body));
+    }
+
+    if body.len() > 0 {
+      // drop the blank line after the last function
+      body.pop();
+
+      try!(stream.write_all(b"impl "));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b" {\n"));
+      try!(stream.write_all(&body));
+      try!(stream.write_all(b"}\n\n"));
+    }
+
+    try!(self.write_upcasts(name, &mut stream));
+
+    if let Some(ctor_name) = copy_ctor {
+      try!(stream.write_all(b"impl std::clone::Clone for "));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b" {\n  fn clone(&self) -> "));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b" { "));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b"::"));
+      try!(write_rs_ident(ctor_name, &mut stream));
+      try!(stream.write_all(b"(self) }\n}\n\n"));
+    }
+
+    if has_default {
+      try!(stream.write_all(b"impl std::default::Default for "));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b" {\n  fn default() -> "));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b" { "));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b"::new() }\n}"));
+      try!(stream.write_all(b"\n\n"));
+    }
+
+    for &(op_name, ref op_desc) in cls.operators.iter() {
+      if op_desc.with_trait {
+        try!(write_operator_trait(name, &c_path, op_name, op_desc, &mut stream));
+      }
+    }
+
+    Ok(())
+  }
+
+  /// writes `CPPContext` and the `Upcast` impls for the class and
+  /// its bases.
+  fn write_upcasts<U: Write>(&self, name: &[u8], stream: &mut U) -> Result<()> {
     try!(stream.write_all(b"impl cpp::CPPContext for "));
     try!(stream.write_all(name));
     try!(stream.write_all(b" {\n  fn mut_ctx(&self) -> *mut c_void { self.ctx }\n}"));
     try!(stream.write_all(b"\n\n"));
 
-    try!(stream.write_all(b"impl std::default::Default for "));
+    try!(stream.write_all(b"impl cpp::Upcast<cpp::"));
+    try!(self.path.generate_c_path(stream));
+    try!(stream.write_all(b"> for "));
     try!(stream.write_all(name));
-    try!(stream.write_all(b" {\n  fn default() -> "));
-    try!(stream.write_all(name));
-    try!(stream.write_all(b" { "));
-    try!(stream.write_all(name));
-    try!(stream.write_all(b"::new() }\n}"));
-    try!(stream.write_all(b"\n\n"));
+    try!(stream.write_all(b" {\n  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }\n}\n\n"));
+
+    for base in self.anon_class.borrow().bases.iter() {
+      try!(stream.write_all(b"impl cpp::Upcast<cpp::"));
+      try!(base.generate_c_path(stream));
+      try!(stream.write_all(b"> for "));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b" {\n  fn mut_base_ctx(&self) -> *mut c_void {\n"));
+      try!(stream.write_all(b"    unsafe { cpp::"));
+      try!(generate_upcast_name(&self.path, base, stream));
+      try!(stream.write_all(b"(self.ctx) }\n  }\n}\n\n"));
+    }
 
     Ok(())
   }
 
-//  pub fn generate_trait(&self,
-//                        filepath: &std::path::Path,
-//                        name: &'a [u8]) -> Result<()> {
-//    let mut stream = try!(self.fstream_from_c_path(filepath));
-//    self.write_trait(name, &mut stream)
-//  }
-//
-//  pub fn write_trait<T: Write>(&self, name: &'a [u8], mut stream: T) -> Result<()> {
-//    unimplemented!()
-//  }
+  pub fn generate_trait(&self,
+                        filepath: &std::path::Path,
+                        name: &'a [u8]) -> Result<()> {
+    let mut fname = try!(self.c_path());
+    fname.insert_str(0, "rust_");
+    fname.push_str(".rs");
+    let mut stream = try!(File::create(filepath.join(fname)));
 
-  pub fn generate_bindings<U: Write, V: Write>(&self, context: &mut Context<U, V>)
-      -> Result<()> {
-    self.anon_class.borrow().generate(&self.namespace,
-                             self.name,
-                             &mut context.cpp_stream,
-                             &mut context.rs_binding_stream)
+    self.write_trait(name, &mut stream)
   }
-}
 
-#[macro_export]
-macro_rules! class_bindings_block {
-  ($cls:expr => { $( $t:tt )* } ) => ({
-    let mut cls = $cls;
-    class_bindings!(cls, $( $t )*)
-  })
-}
+  /// writes the Rust side of a class implementable from Rust: a trait
+  /// `name` with its overridable methods, and `Rust<name>`, which owns
+  /// a C++ object forwarding those methods to a boxed implementation of
+  /// the trait. errors and panics of the implementation are rethrown in
+  /// C++ as exceptions.
+  pub fn write_trait<U: Write>(&self, name: &'a [u8], mut stream: U) -> Result<()> {
+    let cls = self.anon_class.borrow();
+    let mut c_path = Vec::new();
+    try!(self.path.generate_c_path(&mut c_path));
+
+    let mut struct_name = Vec::new();
+    try!(struct_name.write_all(b"Rust"));
+    try!(struct_name.write_all(name));
+
+    try!(stream.write_all(b"pub trait "));
+    try!(stream.write_all(name));
+    try!(stream.write_all(b" {\n"));
+    for &(method_name, ref method_desc) in cls.overridables.iter() {
+      try!(check_overridable(method_name, method_desc));
+      let args = safe_args(method_desc.func.args.as_slice()).unwrap();
+
+      try!(stream.write_all(b"  fn "));
+      try!(write_rs_ident(method_name, &mut stream));
+      try!(stream.write_all(if method_desc.is_const { b"(&self" } else { b"(&mut self" }));
+      if args.len() > 0 {
+        try!(stream.write_all(b", "));
+        try!(write_safe_params(&args, &mut stream));
+      }
+      try!(stream.write_all(b") -> ::Result<"));
+      match &method_desc.func.ret {
+        &proto::BasicType::Simple(proto::CType::Void) => try!(stream.write_all(b"()")),
+        &proto::BasicType::Simple(ref t)              => try!(write_safe_value_type(t, &mut stream)),
+        _                                             => unreachable!()
+      }
+      try!(stream.write_all(b">;\n"));
+    }
+    try!(stream.write_all(b"}\n\n"));
 
-#[macro_export]
-macro_rules! class_bindings {
-  ($cls:expr, ) => (
-    $cls
-  );
+    try!(stream.write_all(b"#[allow(non_snake_case)]\nmod trampolines_"));
+    try!(stream.write_all(&c_path));
+    try!(stream.write_all(b" {\n  #[allow(unused_imports)]\n"));
+    try!(stream.write_all(b"  use libc::{c_void, c_uchar, c_char, c_long, c_longlong, size_t, c_uint, c_int,\n"));
+    try!(stream.write_all(b"             c_double};\n\n  use cpp;\n  use super::"));
+    try!(stream.write_all(name));
+    try!(stream.write_all(b" as Trait;\n\n"));
+
+    try!(stream.write_all(b"  pub fn vtable<T: Trait>() -> cpp::vtable_"));
+    try!(stream.write_all(&c_path));
+    try!(stream.write_all(b" {\n    cpp::vtable_"));
+    try!(stream.write_all(&c_path));
+    try!(stream.write_all(b" {\n"));
+    for &(method_name, _) in cls.overridables.iter() {
+      try!(stream.write_all(b"      "));
+      try!(stream.write_all(method_name));
+      try!(stream.write_all(b": "));
+      try!(stream.write_all(method_name));
+      try!(stream.write_all(b"::<T>,\n"));
+    }
+    try!(stream.write_all(b"      drop: drop::<T>\n    }\n  }\n\n"));
 
-  ($cls:expr, mutable methods { $( $t:tt )* }  $( $rest:tt )* ) => ({
-    let mut cls = class_methods!($cls, false, $( $t )*);
-    class_bindings!(cls, $( $rest )* )
-  });
+    for &(method_name, ref method_desc) in cls.overridables.iter() {
+      let function_desc = &method_desc.func;
+      let args = safe_args(function_desc.args.as_slice()).unwrap();
 
-  ($cls:expr, constant methods { $( $t:tt )* }  $( $rest:tt )* ) => ({
-    let mut cls = class_methods!($cls, true, $( $t )*);
-    class_bindings!(cls, $( $rest )* )
-  });
+      try!(stream.write_all(b"  extern \"C\" fn "));
+      try!(stream.write_all(method_name));
+      try!(stream.write_all(b"<T: Trait>(this: *mut c_void, "));
+      if function_desc.args.len() > 0 {
+        try!(function_desc.args.generate_proto_rs(&mut stream));
+        try!(stream.write_all(b", "));
+      }
+      try!(stream.write_all(b"exc: *mut cpp::Exception)"));
+      if !function_desc.ret.is_void() {
+        try!(stream.write_all(b" -> "));
+        try!(function_desc.ret.generate_rs(&mut stream));
+      }
+      try!(stream.write_all(b" {\n"));
+      try!(stream.write_all(if method_desc.is_const {
+        &b"    let this = unsafe { &*(this as *const T) };\n"[..]
+      } else {
+        &b"    let this = unsafe { &mut *(this as *mut T) };\n"[..]
+      }));
 
-  ($cls:expr, constructors { $( $t:tt )* }  $( $rest:tt )* ) => ({
-    let mut cls = class_ctors!($cls, $( $t )*);
-    class_bindings!(cls, $( $rest )* )
-  });
-}
+      // rebuild the safe arguments from the C ones, a slice takes two
+      let mut c_arg = 0;
+      for (i, arg) in args.iter().enumerate() {
+        match arg {
+          &SafeArg::Slice(is_mut) => {
+            try!(write!(stream, "    let arg{} = unsafe {{ cpp::{}(arg{}, arg{}) }};\n",
+                        i, if is_mut { "slice_mut" } else { "slice" }, c_arg, c_arg + 1));
+            c_arg += 2;
+          },
+          _ => {
+            try!(write!(stream, "    let arg{} = arg{};\n", i, c_arg));
+            c_arg += 1;
+          }
+        }
+      }
 
+      try!(stream.write_all(b"    cpp::callback(exc, move || this."));
+      try!(write_rs_ident(method_name, &mut stream));
+      try!(stream.write_all(b"("));
+      for i in 0..args.len() {
+        if i > 0 {
+          try!(stream.write_all(b", "));
+        }
+        try!(write!(stream, "arg{}", i));
+      }
+      try!(stream.write_all(b"))\n  }\n\n"));
+    }
+
+    try!(stream.write_all(b"  pub extern \"C\" fn drop<T: Trait>(this: *mut c_void) {\n"));
+    try!(stream.write_all(b"    unsafe { ::std::mem::drop(Box::from_raw(this as *mut T)) };\n  }\n}\n\n"));
+
+    try!(stream.write_all(b"pub struct "));
+    try!(stream.write_all(&struct_name));
+    try!(stream.write_all(b" {\n  ctx: *mut c_void\n}\n"));
+
+    try!(stream.write_all(b"impl Drop for "));
+    try!(stream.write_all(&struct_name));
+    try!(stream.write_all(b" {\n  fn drop(&mut self) {\n"));
+    try!(stream.write_all(b"    unsafe { cpp::del_"));
+    try!(stream.write_all(&c_path));
+    try!(stream.write_all(b"(self.ctx) };\n  }\n}\n"));
+
+    try!(stream.write_all(b"impl "));
+    try!(stream.write_all(&struct_name));
+    try!(stream.write_all(b" {\n  pub fn try_new<T: "));
+    try!(stream.write_all(name));
+    try!(stream.write_all(b" + 'static>(imp: T) -> ::Result<"));
+    try!(stream.write_all(&struct_name));
+    try!(stream.write_all(b"> {\n"));
+    try!(stream.write_all(b"    let this = Box::into_raw(Box::new(imp)) as *mut c_void;\n"));
+    try!(stream.write_all(b"    let vtable = trampolines_"));
+    try!(stream.write_all(&c_path));
+    try!(stream.write_all(b"::vtable::<T>();\n"));
+    try!(stream.write_all(b"    let ctx = match cpp::catch(|exc| unsafe { cpp::new_rust_"));
+    try!(stream.write_all(&c_path));
+    try!(stream.write_all(b"(this, &vtable, exc) }) {\n"));
+    try!(stream.write_all(b"      Ok(ctx) => ctx,\n      Err(e)  => {\n"));
+    try!(stream.write_all(b"        (vtable.drop)(this);\n        return Err(::Error::from(e));\n"));
+    try!(stream.write_all(b"      }\n    };\n\n    Ok("));
+    try!(stream.write_all(&struct_name));
+    try!(stream.write_all(b" { ctx: ctx })\n  }\n\n  pub fn new<T: "));
+    try!(stream.write_all(name));
+    try!(stream.write_all(b" + 'static>(imp: T) -> "));
+    try!(stream.write_all(&struct_name));
+    try!(stream.write_all(b" {\n    "));
+    try!(stream.write_all(&struct_name));
+    try!(stream.write_all(b"::try_new(imp).unwrap()\n  }\n}\n\n"));
+
+    try!(self.write_upcasts(&struct_name, &mut stream));
+
+    Ok(())
+  }
+
+  pub fn generate_bindings<U: Write, V: Write, W: Write>(&self, context: &mut Context<U, V, W>)
+      -> Result<()> {
+    self.anon_class.borrow().generate(&self.path,
+                                      &mut context.cpp_stream,
+                                      &mut context.rs_binding_stream,
+                                      &mut context.c_header_stream)
+  }
+}
+
+/// how the safe wrapper of a shim takes one argument, or a pointer
+/// and `size_t` pair of arguments.
+enum SafeArg<'b> {
+  /// a `&[u8]`, or a `&mut [u8]` when the flag is set.
+  Slice(bool),
+  Value(&'b proto::CType),
+  /// an `Upcast` trait object for the class with the given Rust name.
+  Object(Vec<u8>, &'b proto::BasicType),
+  /// the Rust enum with the given name, in scope where the wrapper is.
+  Enum(&'b [u8]),
+  /// a `std::string`, taken as a `&str` when the flag is set and as a
+  /// `&[u8]` otherwise.
+  String(bool)
+}
+
+/// what the safe wrapper of a shim returns inside `::Result`.
+enum SafeRet<'b> {
+  Unit,
+  Value(&'b proto::CType),
+  /// a new instance of the wrapper itself.
+  Wrapper,
+  /// a copy of a `std::string`, a `String` when the flag is set and a
+  /// `Vec<u8>` otherwise.
+  String(bool)
+}
+
+const RS_KEYWORDS: &'static [&'static [u8]] = &[
+  b"abstract", b"alignof", b"as", b"become", b"box", b"break", b"const",
+  b"continue", b"crate", b"do", b"else", b"enum", b"extern", b"false",
+  b"final", b"fn", b"for", b"if", b"impl", b"in", b"let", b"loop",
+  b"macro", b"match", b"mod", b"move", b"mut", b"offsetof", b"override",
+  b"priv", b"proc", b"pub", b"pure", b"ref", b"return", b"self",
+  b"sizeof", b"static", b"struct", b"super", b"trait", b"true", b"type",
+  b"typeof", b"unsafe", b"unsized", b"use", b"virtual", b"where",
+  b"while", b"yield",
+];
+
+/// writes `name` in snake case, `MinEncodedSize` becomes
+/// `min_encoded_size`. a name that is a Rust keyword gets a trailing `_`.
+fn write_rs_ident(name: &[u8], out: &mut Write) -> Result<()> {
+  fn is_upper(b: u8) -> bool { b >= b'A' && b <= b'Z' }
+  fn is_lower(b: u8) -> bool { (b >= b'a' && b <= b'z') || (b >= b'0' && b <= b'9') }
+
+  let mut ident = Vec::new();
+  for (i, &b) in name.iter().enumerate() {
+    if is_upper(b) && i > 0 {
+      let prev = name[i - 1];
+      let next_lower = i + 1 < name.len() && is_lower(name[i + 1]);
+      if is_lower(prev) || (is_upper(prev) && next_lower) {
+        ident.push(b'_');
+      }
+    }
+
+    ident.push(if is_upper(b) { b - b'A' + b'a' } else { b });
+  }
+
+  try!(out.write_all(&ident));
+  if RS_KEYWORDS.contains(&&ident[..]) {
+    try!(out.write_all(b"_"));
+  }
+
+  Ok(())
+}
+
+/// the Rust name of the bound class spelled `cpp_name` in a prototype,
+/// if it is a plain, possibly namespaced, class name.
+fn rs_class_name(cpp_name: &[u8]) -> Option<Vec<u8>> {
+  let mut parts = Vec::new();
+  let mut rest = cpp_name;
+  loop {
+    let end = rest.windows(2).position(|w| w == b"::").unwrap_or(rest.len());
+    let part = &rest[..end];
+    let is_ident = part.len() > 0 && part.iter().all(|&b| {
+      b == b'_' || (b >= b'a' && b <= b'z') || (b >= b'A' && b <= b'Z') || (b >= b'0' && b <= b'9')
+    });
+    if !is_ident {
+      return None;
+    }
+    parts.push(part);
+
+    if end == rest.len() {
+      break;
+    }
+    rest = &rest[end + 2..];
+  }
+
+  let name = parts.pop().unwrap();
+  let mut c_path = Vec::new();
+  match class_path(parts, name).generate_c_path(&mut c_path) {
+    Ok(()) => Some(c_path),
+    Err(_) => None
+  }
+}
+
+/// the Rust name of the C++ enum `cpp_name`, its last part.
+fn rs_enum_name(cpp_name: &[u8]) -> &[u8] {
+  match cpp_name.windows(2).rposition(|w| w == b"::") {
+    Some(i) => &cpp_name[i + 2..],
+    None    => cpp_name
+  }
+}
+
+fn is_byte(t: &proto::CType) -> bool {
+  match t {
+    &proto::CType::Byte  |
+    &proto::CType::UChar => true,
+    _                    => false
+  }
+}
+
+fn safe_args<'b>(args: &'b [proto::BasicType]) -> Option<Vec<SafeArg<'b>>> {
+  use proto::BasicType::*;
+
+  let mut safe = Vec::new();
+  let mut i = 0;
+  while i < args.len() {
+    let followed_by_size = match args.get(i + 1) {
+      Some(&Simple(proto::CType::SizeT)) => true,
+      _                                  => false
+    };
+
+    match &args[i] {
+      &ConstPointer(ref t) if is_byte(t) && followed_by_size => {
+        safe.push(SafeArg::Slice(false));
+        i += 1;
+      },
+      &MutPointer(ref t) if is_byte(t) && followed_by_size => {
+        safe.push(SafeArg::Slice(true));
+        i += 1;
+      },
+      &Simple(proto::CType::Void)       |
+      &Simple(proto::CType::Custom(_))  => return None,
+      &Simple(proto::CType::Enum(n))    => safe.push(SafeArg::Enum(rs_enum_name(n))),
+      &Simple(proto::CType::StdString)   |
+      &ConstRef(proto::CType::StdString) => safe.push(SafeArg::String(false)),
+      &Simple(proto::CType::Utf8String)   |
+      &ConstRef(proto::CType::Utf8String) => safe.push(SafeArg::String(true)),
+      &Simple(ref t)                    => safe.push(SafeArg::Value(t)),
+      &ConstRef(proto::CType::Custom(n)) |
+      &MutRef(proto::CType::Custom(n))   |
+      &Owned(proto::CType::Custom(n))    => match rs_class_name(n) {
+        Some(rs_name) => safe.push(SafeArg::Object(rs_name, &args[i])),
+        None          => return None
+      },
+      _ => return None
+    }
+
+    i += 1;
+  }
+
+  Some(safe)
+}
+
+fn safe_ret<'b>(ret: &'b proto::BasicType, c_path: &[u8]) -> Option<SafeRet<'b>> {
+  use proto::BasicType::*;
+
+  match ret {
+    &Simple(proto::CType::Void)      => Some(SafeRet::Unit),
+    &Simple(proto::CType::Custom(_)) |
+    &Simple(proto::CType::Enum(_))   => None,
+    &Simple(proto::CType::StdString)    |
+    &ConstRef(proto::CType::StdString)  => Some(SafeRet::String(false)),
+    &Simple(proto::CType::Utf8String)   |
+    &ConstRef(proto::CType::Utf8String) => Some(SafeRet::String(true)),
+    &Simple(ref t)                   => Some(SafeRet::Value(t)),
+    &Owned(proto::CType::Custom(n))  => match rs_class_name(n) {
+      Some(ref rs_name) if &rs_name[..] == c_path => Some(SafeRet::Wrapper),
+      _                                          => None
+    },
+    _ => None
+  }
+}
+
+fn is_copy_ctor(args: &[SafeArg], c_path: &[u8]) -> bool {
+  if args.len() != 1 {
+    return false;
+  }
+
+  match &args[0] {
+    &SafeArg::Object(ref rs_name, &proto::BasicType::ConstRef(_)) => &rs_name[..] == c_path,
+    _                                                             => false
+  }
+}
+
+/// methods implemented from Rust may only take and return values and
+/// byte slices.
+fn check_overridable(name: &[u8], method_desc: &Method) -> Result<()> {
+  let function_desc = &method_desc.func;
+  let args_ok = match safe_args(function_desc.args.as_slice()) {
+    Some(args) => args.iter().all(|arg| match arg {
+      &SafeArg::Object(..) |
+      &SafeArg::Enum(_)    |
+      &SafeArg::String(_)  => false,
+      _                    => true
+    }),
+    None       => false
+  };
+  let ret_ok = match &function_desc.ret {
+    &proto::BasicType::Simple(proto::CType::Custom(_)) => false,
+    ret if ret.is_string()                             => false,
+    &proto::BasicType::Simple(_)                       => true,
+    _                                                  => false
+  };
+
+  if args_ok && ret_ok {
+    Ok(())
+  } else {
+    Err(Error::Unexpected(format!("overridable method {} has no safe Rust form",
+                                  String::from_utf8_lossy(name))))
+  }
+}
+
+/// a `std::string` can only be passed or returned by value or by
+/// `const` reference.
+fn check_strings(name: &[u8], ret: &proto::BasicType, args: &FunctionArgs) -> Result<()> {
+  use proto::BasicType::*;
+
+  for t in Some(ret).into_iter().chain(args.as_slice().iter()) {
+    let ctype = match t {
+      &Simple(ref c) | &MutPointer(ref c) | &ConstPointer(ref c) |
+      &MutRef(ref c) | &ConstRef(ref c)   | &Owned(ref c)        => c
+    };
+    let is_string = match ctype {
+      &proto::CType::StdString  |
+      &proto::CType::Utf8String => true,
+      _                         => false
+    };
+
+    if is_string && !t.is_string() {
+      return Err(Error::Unexpected(format!("{} takes or returns a std::string \
+                                            other than by value or const reference",
+                                           String::from_utf8_lossy(name))));
+    }
+  }
+
+  Ok(())
+}
+
+/// operators take as many arguments as their C++ counterpart,
+/// comparisons return `bool` and compound assignments nothing.
+fn check_operator(name: &[u8], op_desc: &OperatorMethod) -> Result<()> {
+  let op = op_desc.op;
+  let function_desc = &op_desc.func;
+  let arity = if op == Operator::Neg { 0 } else { 1 };
+  let ret_ok = match &function_desc.ret {
+    &proto::BasicType::Simple(proto::CType::Bool) => op.is_comparison(),
+    &proto::BasicType::Simple(proto::CType::Void) => op.is_assign(),
+    _                                             => !op.is_comparison() && !op.is_assign()
+  };
+
+  if function_desc.args.len() == arity && ret_ok {
+    Ok(())
+  } else {
+    Err(Error::Unexpected(format!("operator {} does not match the prototype of `{}`",
+                                  String::from_utf8_lossy(name),
+                                  String::from_utf8_lossy(op.cpp_token()))))
+  }
+}
+
+fn write_safe_value_type(t: &proto::CType, out: &mut Write) -> Result<()> {
+  let mut rs_type = Vec::new();
+  try!(t.generate_rs(&mut rs_type));
+  if rs_type.starts_with(b"c_") || &rs_type[..] == b"size_t" {
+    try!(out.write_all(b"::libc::"));
+  }
+  try!(out.write_all(&rs_type));
+
+  Ok(())
+}
+
+fn write_safe_ret_type(name: &[u8], ret: &SafeRet, out: &mut Write) -> Result<()> {
+  match ret {
+    &SafeRet::Unit          => try!(out.write_all(b"()")),
+    &SafeRet::Value(t)      => try!(write_safe_value_type(t, out)),
+    &SafeRet::Wrapper       => try!(out.write_all(name)),
+    &SafeRet::String(false) => try!(out.write_all(b"Vec<u8>")),
+    &SafeRet::String(true)  => try!(out.write_all(b"String"))
+  }
+
+  Ok(())
+}
+
+fn write_safe_params(args: &[SafeArg], out: &mut Write) -> Result<()> {
+  for (i, arg) in args.iter().enumerate() {
+    if i > 0 {
+      try!(out.write_all(b", "));
+    }
+    try!(write!(out, "arg{}: ", i));
+
+    match arg {
+      &SafeArg::Slice(false)                => try!(out.write_all(b"&[u8]")),
+      &SafeArg::Slice(true)                 => try!(out.write_all(b"&mut [u8]")),
+      &SafeArg::Value(t)                    => try!(write_safe_value_type(t, out)),
+      &SafeArg::Enum(rs_name)               => try!(out.write_all(rs_name)),
+      &SafeArg::String(false)               => try!(out.write_all(b"&[u8]")),
+      &SafeArg::String(true)                => try!(out.write_all(b"&str")),
+      &SafeArg::Object(ref rs_name, btype)  => {
+        try!(out.write_all(if let &proto::BasicType::MutRef(_) = btype {
+          b"&mut cpp::Upcast<cpp::"
+        } else {
+          b"&cpp::Upcast<cpp::"
+        }));
+        try!(out.write_all(rs_name));
+        try!(out.write_all(b">"));
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// writes the shim arguments for `args`, each followed by `, `.
+fn write_safe_apply(args: &[SafeArg], out: &mut Write) -> Result<()> {
+  for (i, arg) in args.iter().enumerate() {
+    match arg {
+      &SafeArg::Slice(false) =>
+        try!(write!(out, "arg{0}.as_ptr(), arg{0}.len() as ::libc::size_t, ", i)),
+      &SafeArg::Slice(true) =>
+        try!(write!(out, "arg{0}.as_mut_ptr(), arg{0}.len() as ::libc::size_t, ", i)),
+      &SafeArg::Value(_) =>
+        try!(write!(out, "arg{}, ", i)),
+      &SafeArg::Enum(_) =>
+        try!(write!(out, "arg{} as ::libc::c_int, ", i)),
+      &SafeArg::String(false) =>
+        try!(write!(out, "cpp::CppString::borrow(arg{}), ", i)),
+      &SafeArg::String(true) =>
+        try!(write!(out, "cpp::CppString::borrow(arg{}.as_bytes()), ", i)),
+      &SafeArg::Object(_, &proto::BasicType::ConstRef(_)) =>
+        try!(write!(out, "cpp::Upcast::base_ctx(arg{}), ", i)),
+      &SafeArg::Object(..) =>
+        try!(write!(out, "cpp::Upcast::mut_base_ctx(arg{}), ", i)),
+    }
+  }
+
+  Ok(())
+}
+
+/// writes `try_<ctor>` and `<ctor>`, which panics on errors. the
+/// constructor named `""` becomes `try_new` and `new`.
+fn write_safe_ctor(name: &[u8],
+                   c_path: &[u8],
+                   ctor_name: &[u8],
+                   args: &[SafeArg],
+                   out: &mut Write) -> Result<()> {
+  let mut rs_name = Vec::new();
+  try!(write_rs_ident(if ctor_name.len() > 0 { ctor_name } else { b"new" },
+                      &mut rs_name));
+
+  try!(out.write_all(b"  pub fn try_"));
+  try!(out.write_all(&rs_name));
+  try!(out.write_all(b"("));
+  try!(write_safe_params(args, out));
+  try!(out.write_all(b") -> ::Result<"));
+  try!(out.write_all(name));
+  try!(out.write_all(b"> {\n    let ctx = try!(cpp::catch(|exc| unsafe { cpp::new_"));
+  if ctor_name.len() > 0 {
+    try!(out.write_all(ctor_name));
+    try!(out.write_all(b"_"));
+  }
+  try!(out.write_all(c_path));
+  try!(out.write_all(b"("));
+  try!(write_safe_apply(args, out));
+  try!(out.write_all(b"exc) }));\n\n    Ok("));
+  try!(out.write_all(name));
+  try!(out.write_all(b" { ctx: ctx })\n  }\n\n"));
+
+  try!(out.write_all(b"  pub fn "));
+  try!(out.write_all(&rs_name));
+  try!(out.write_all(b"("));
+  try!(write_safe_params(args, out));
+  try!(out.write_all(b") -> "));
+  try!(out.write_all(name));
+  try!(out.write_all(b" {\n    "));
+  try!(out.write_all(name));
+  try!(out.write_all(b"::try_"));
+  try!(out.write_all(&rs_name));
+  try!(out.write_all(b"("));
+  for i in 0..args.len() {
+    if i > 0 {
+      try!(out.write_all(b", "));
+    }
+    try!(write!(out, "arg{}", i));
+  }
+  try!(out.write_all(b").unwrap()\n  }\n\n"));
+
+  Ok(())
+}
+
+/// writes a safe wrapper calling `shim_name`, a method when `receiver`
+/// holds its constness and an associated function otherwise. nothing
+/// is written for functions without a safe form.
+fn write_safe_fn(name: &[u8],
+                 c_path: &[u8],
+                 fn_name: &[u8],
+                 receiver: Option<bool>,
+                 shim_name: &[u8],
+                 function_desc: &Function,
+                 out: &mut Write) -> Result<()> {
+  let (args, ret) = match (safe_args(function_desc.args.as_slice()),
+                           safe_ret(&function_desc.ret, c_path)) {
+    (Some(args), Some(ret)) => (args, ret),
+    _                       => return Ok(())
+  };
+
+  try!(out.write_all(b"  pub fn "));
+  try!(write_rs_ident(fn_name, out));
+  try!(out.write_all(b"("));
+  match receiver {
+    Some(true)  => try!(out.write_all(b"&self")),
+    Some(false) => try!(out.write_all(b"&mut self")),
+    None        => ()
+  }
+  if receiver.is_some() && args.len() > 0 {
+    try!(out.write_all(b", "));
+  }
+  try!(write_safe_params(&args, out));
+  try!(out.write_all(b") -> ::Result<"));
+  try!(write_safe_ret_type(name, &ret, out));
+  try!(out.write_all(b"> {\n    let ret = try!(cpp::catch(|exc| unsafe {\n      cpp::"));
+  try!(out.write_all(shim_name));
+  try!(out.write_all(b"("));
+  if receiver.is_some() {
+    try!(out.write_all(b"self.ctx, "));
+  }
+  try!(write_safe_apply(&args, out));
+  try!(out.write_all(b"exc)\n    }));\n\n    Ok("));
+  match ret {
+    SafeRet::Wrapper       => {
+      try!(out.write_all(name));
+      try!(out.write_all(b" { ctx: ret }"));
+    },
+    SafeRet::String(false) => try!(out.write_all(b"unsafe { ret.into_vec() }")),
+    SafeRet::String(true)  => try!(out.write_all(b"unsafe { ret.into_string() }")),
+    _                      => try!(out.write_all(b"ret"))
+  }
+  try!(out.write_all(b")\n  }\n\n"));
+
+  Ok(())
+}
+
+/// implements the Rust trait of an operator through its `try_*`
+/// wrapper, panicking on errors. the right hand side must be a value
+/// or a `const` reference to the class itself, which the trait then
+/// takes by reference, as does the left hand side of the operators
+/// returning a new object.
+fn write_operator_trait(name: &[u8],
+                        c_path: &[u8],
+                        op_name: &[u8],
+                        op_desc: &OperatorMethod,
+                        out: &mut Write) -> Result<()> {
+  let op = op_desc.op;
+  let function_desc = &op_desc.func;
+  let no_trait = || {
+    Error::Unexpected(format!("operator {} has no Rust trait form",
+                              String::from_utf8_lossy(op_name)))
+  };
+
+  try!(check_operator(op_name, op_desc));
+  let args = try!(safe_args(function_desc.args.as_slice()).ok_or_else(&no_trait));
+  let ret = try!(safe_ret(&function_desc.ret, c_path).ok_or_else(&no_trait));
+
+  // the right hand side, and whether it is the class itself
+  let mut rhs = Vec::new();
+  let by_ref = match args.get(0) {
+    None                                                     => false,
+    Some(&SafeArg::Value(t))                                 => {
+      try!(write_safe_value_type(t, &mut rhs));
+      false
+    },
+    Some(&SafeArg::Object(ref rs_name, &proto::BasicType::ConstRef(_))) |
+    Some(&SafeArg::Object(ref rs_name, &proto::BasicType::Owned(_)))
+        if &rs_name[..] == c_path                            => {
+      try!(rhs.write_all(b"&'b "));
+      try!(rhs.write_all(name));
+      true
+    },
+    _                                                        => return Err(no_trait())
+  };
+  if op.is_comparison() && !by_ref {
+    return Err(no_trait());
+  }
+
+  let mut try_name = Vec::new();
+  try!(try_name.write_all(b"try_"));
+  try!(write_rs_ident(op_name, &mut try_name));
+
+  // comparisons compare with the class itself, the default of the
+  // trait, and are implemented for it rather than for references
+  let on_ref = !op.is_assign() && !op.is_comparison();
+  try!(out.write_all(match (on_ref, by_ref && !op.is_comparison()) {
+    (true, true)   => &b"impl<'a, 'b> "[..],
+    (true, false)  => &b"impl<'a> "[..],
+    (false, true)  => &b"impl<'b> "[..],
+    (false, false) => &b"impl "[..]
+  }));
+  try!(out.write_all(op.rs_trait()));
+  if rhs.len() > 0 && !op.is_comparison() {
+    try!(out.write_all(b"<"));
+    try!(out.write_all(&rhs));
+    try!(out.write_all(b">"));
+  }
+  try!(out.write_all(if on_ref { &b" for &'a "[..] } else { &b" for "[..] }));
+  try!(out.write_all(name));
+  try!(out.write_all(b" {\n"));
+
+  match op {
+    Operator::Eq => {
+      try!(out.write_all(b"  fn eq(&self, other: &"));
+      try!(out.write_all(name));
+      try!(out.write_all(b") -> bool {\n    self."));
+      try!(out.write_all(&try_name));
+      try!(out.write_all(b"(other).unwrap()\n  }\n"));
+    },
+    Operator::Lt => {
+      try!(out.write_all(b"  fn partial_cmp(&self, other: &"));
+      try!(out.write_all(name));
+      try!(out.write_all(b") -> Option<std::cmp::Ordering> {\n    if self."));
+      try!(out.write_all(&try_name));
+      try!(out.write_all(b"(other).unwrap() {\n      Some(std::cmp::Ordering::Less)\n    } else if other."));
+      try!(out.write_all(&try_name));
+      try!(out.write_all(b"(self).unwrap() {\n      Some(std::cmp::Ordering::Greater)\n    } else {\n"));
+      try!(out.write_all(b"      Some(std::cmp::Ordering::Equal)\n    }\n  }\n"));
+    },
+    _ if op.is_assign() => {
+      try!(out.write_all(b"  fn "));
+      try!(out.write_all(op.name()));
+      try!(out.write_all(b"(&mut self, rhs: "));
+      try!(out.write_all(&rhs));
+      try!(out.write_all(b") {\n    self."));
+      try!(out.write_all(&try_name));
+      try!(out.write_all(b"(rhs).unwrap()\n  }\n"));
+    },
+    _ => {
+      let mut output = Vec::new();
+      try!(write_safe_ret_type(name, &ret, &mut output));
+
+      try!(out.write_all(b"  type Output = "));
+      try!(out.write_all(&output));
+      try!(out.write_all(b";\n\n  fn "));
+      try!(out.write_all(op.name()));
+      try!(out.write_all(b"(self"));
+      if rhs.len() > 0 {
+        try!(out.write_all(b", rhs: "));
+        try!(out.write_all(&rhs));
+      }
+      try!(out.write_all(b") -> "));
+      try!(out.write_all(&output));
+      try!(out.write_all(b" {\n    self."));
+      try!(out.write_all(&try_name));
+      try!(out.write_all(if rhs.len() > 0 { &b"(rhs)"[..] } else { &b"()"[..] }));
+      try!(out.write_all(b".unwrap()\n  }\n"));
+    }
+  }
+  try!(out.write_all(b"}\n\n"));
+
+  Ok(())
+}
+
+#[macro_export]
+macro_rules! namespace {
+  ( $ns:expr => $b:tt ) => ({
+    let anon_class = class_bindings_block!($crate::class() => $b);
+    $crate::Namespace::new(
+      $ns,
+      anon_class
+    )
+  });
+}
+
+/// free functions living in a namespace rather than next to a class.
+/// only the `functions` bindings of `anon_class` are generated.
+pub struct Namespace<'a, T> {
+  pub namespace:  Vec<&'a [u8]>,
+  pub anon_class: T,
+}
+
+impl<'a, T: Borrow<Class>> Namespace<'a, T> {
+  pub fn new(namespace:  Vec<&'a [u8]>,
+             anon_class: T) -> Namespace<'a, T> {
+    Namespace {
+      namespace: namespace,
+      anon_class: anon_class,
+    }
+  }
+
+  pub fn generate_bindings<U: Write, V: Write, W: Write>(&self, context: &mut Context<U, V, W>)
+      -> Result<()> {
+    let cls = self.anon_class.borrow();
+
+    try!(cls.check_strings());
+    try!(cls.generate_cpp_functions(&self.namespace, &mut context.cpp_stream));
+
+    let header_stream = &mut context.c_header_stream;
+    try!(header_stream.write_all(b"/* namespace "));
+    try!(header_stream.write_all(&self.namespace.join(&b"::"[..])));
+    try!(header_stream.write_all(b" */\n"));
+    try!(generate_c_handles(&try!(cls.c_handles(None)), header_stream));
+    try!(cls.generate_c_header_functions(&self.namespace, header_stream));
+    try!(header_stream.write_all(b"\n"));
+
+    let rs_stream = &mut context.rs_binding_stream;
+    try!(rs_stream.write_all(b"extern {\n"));
+    try!(cls.generate_rs_functions(&self.namespace, rs_stream));
+    try!(rs_stream.write_all(b"}\n"));
+
+    Ok(())
+  }
+}
+
+#[macro_export]
+macro_rules! class_bindings_block {
+  ($cls:expr => { $( $t:tt )* } ) => ({
+    let mut cls = $cls;
+    class_bindings!(cls, $( $t )*)
+  })
+}
+
+#[macro_export]
+macro_rules! class_bindings {
+  ($cls:expr, ) => (
+    $cls
+  );
+
+  ($cls:expr, mutable methods { $( $t:tt )* }  $( $rest:tt )* ) => ({
+    let mut cls = class_methods!($cls, false, $( $t )*);
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, constant methods { $( $t:tt )* }  $( $rest:tt )* ) => ({
+    let mut cls = class_methods!($cls, true, $( $t )*);
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, bases { $( $base:expr ; )* }  $( $rest:tt )* ) => ({
+    let mut cls = $cls;
+    $( cls.add_base($base); )*
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, overridable mutable methods { $( $t:tt )* }  $( $rest:tt )* ) => ({
+    let mut cls = class_overridables!($cls, false, $( $t )*);
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, overridable constant methods { $( $t:tt )* }  $( $rest:tt )* ) => ({
+    let mut cls = class_overridables!($cls, true, $( $t )*);
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, constructors { $( $t:tt )* }  $( $rest:tt )* ) => ({
+    let mut cls = class_ctors!($cls, $( $t )*);
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, static methods { $( $t:tt )* }  $( $rest:tt )* ) => ({
+    let mut cls = class_functions!($cls, add_static_method_as, $( $t )*);
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, functions { $( $t:tt )* }  $( $rest:tt )* ) => ({
+    let mut cls = class_functions!($cls, add_function_as, $( $t )*);
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, operators { $( $t:tt )* }  $( $rest:tt )* ) => ({
+    let mut cls = class_operators!($cls, false, $( $t )*);
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, operators with traits { $( $t:tt )* }  $( $rest:tt )* ) => ({
+    let mut cls = class_operators!($cls, true, $( $t )*);
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, enums { $( $name:expr => $( $variant:expr ),+ ; )* }  $( $rest:tt )* ) => ({
+    let mut cls = $cls;
+    $( cls.add_enum($name, vec![ $( $variant ),+ ]); )*
+    class_bindings!(cls, $( $rest )* )
+  });
+
+  ($cls:expr, constants { $( $ctype:expr , $name:expr ; )* }  $( $rest:tt )* ) => ({
+    let mut cls = $cls;
+    $( cls.add_constant($name, $ctype); )*
+    class_bindings!(cls, $( $rest )* )
+  });
+}
+
+/// each entry is `return type, operator, argument type;` where the
+/// operator is a variant of `Operator`, `Neg` taking no argument. an
+/// overload is exported under its own name with `operator => alias`.
+#[macro_export]
+macro_rules! class_operators {
+  ($cls:expr , $with_trait:expr , ) => (
+    $cls
+  );
+
+  ($cls:expr , $with_trait:expr , $rtype:expr , $op:ident ; $( $rest:tt )* ) => ({
+    $cls.add_operator($crate::Operator::$op, $with_trait, function!($rtype) );
+    class_operators!($cls, $with_trait, $( $rest )* )
+  });
+
+  ($cls:expr , $with_trait:expr , $rtype:expr , $op:ident, $( $args:expr ),+ ; $( $rest:tt )* ) => ({
+    $cls.add_operator($crate::Operator::$op, $with_trait, function!($rtype, $( $args ),+ ) );
+    class_operators!($cls, $with_trait, $( $rest )* )
+  });
+
+  ($cls:expr , $with_trait:expr , $rtype:expr , $op:ident => $alias:expr ; $( $rest:tt )* ) => ({
+    $cls.add_operator_as($alias, $crate::Operator::$op, $with_trait, function!($rtype) );
+    class_operators!($cls, $with_trait, $( $rest )* )
+  });
+
+  ($cls:expr , $with_trait:expr , $rtype:expr , $op:ident => $alias:expr, $( $args:expr ),+ ; $( $rest:tt )* ) => ({
+    $cls.add_operator_as($alias, $crate::Operator::$op, $with_trait, function!($rtype, $( $args ),+ ) );
+    class_operators!($cls, $with_trait, $( $rest )* )
+  });
+}
+
+/// like `class_methods!`, for bindings that take no receiver. `$add`
+/// is the `Class` method that records each binding under an exported
+/// name.
+#[macro_export]
+macro_rules! class_functions {
+  ($cls:expr , $add:ident , ) => (
+    $cls
+  );
+
+  ($cls:expr , $add:ident , $rtype:expr , $fname:expr ; $( $rest:tt )* ) => ({
+    $cls.$add($fname, $fname, function!($rtype) );
+    class_functions!($cls, $add, $( $rest )* )
+  });
+
+  ($cls:expr , $add:ident , $rtype:expr , $fname:expr, $( $args:expr ),+ ; $( $rest:tt )* ) => ({
+    $cls.$add($fname, $fname, function!($rtype, $( $args ),+ ) );
+    class_functions!($cls, $add, $( $rest )* )
+  });
+
+  ($cls:expr , $add:ident , $rtype:expr , $fname:expr => $alias:expr ; $( $rest:tt )* ) => ({
+    $cls.$add($alias, $fname, function!($rtype) );
+    class_functions!($cls, $add, $( $rest )* )
+  });
+
+  ($cls:expr , $add:ident , $rtype:expr , $fname:expr => $alias:expr, $( $args:expr ),+ ; $( $rest:tt )* ) => ({
+    $cls.$add($alias, $fname, function!($rtype, $( $args ),+ ) );
+    class_functions!($cls, $add, $( $rest )* )
+  });
+}
+
+/// each entry is `return type, name, argument types...;`. an overload
+/// can be exported under its own name with `return type, name => alias,
+/// argument types...;`, the shim still calls `name`.
 #[macro_export]
 macro_rules! class_methods {
   ($cls:expr , $is_const:expr , ) => (
@@ -356,6 +1928,44 @@ macro_rules! class_methods {
     $cls.add_method($mname, $is_const, function!($rtype, $( $args ),+ ) );
     class_methods!($cls, $is_const, $( $rest )* )
   });
+
+  ($cls:expr , $is_const:expr , $rtype:expr , $mname:expr => $alias:expr ; $( $rest:tt )* ) => ({
+    $cls.add_method_as($alias, $mname, $is_const, function!($rtype) );
+    class_methods!($cls, $is_const, $( $rest )* )
+  });
+
+  ($cls:expr , $is_const:expr , $rtype:expr , $mname:expr => $alias:expr, $( $args:expr ),+ ; $( $rest:tt )* ) => ({
+    $cls.add_method_as($alias, $mname, $is_const, function!($rtype, $( $args ),+ ) );
+    class_methods!($cls, $is_const, $( $rest )* )
+  });
+}
+
+/// like `class_methods!`, for virtual methods implemented from Rust.
+#[macro_export]
+macro_rules! class_overridables {
+  ($cls:expr , $is_const:expr , ) => (
+    $cls
+  );
+
+  ($cls:expr , $is_const:expr , $rtype:expr , $mname:expr ; $( $rest:tt )* ) => ({
+    $cls.add_overridable_method($mname, $is_const, function!($rtype) );
+    class_overridables!($cls, $is_const, $( $rest )* )
+  });
+
+  ($cls:expr , $is_const:expr , $rtype:expr , $mname:expr, $( $args:expr ),+ ; $( $rest:tt )* ) => ({
+    $cls.add_overridable_method($mname, $is_const, function!($rtype, $( $args ),+ ) );
+    class_overridables!($cls, $is_const, $( $rest )* )
+  });
+
+  ($cls:expr , $is_const:expr , $rtype:expr , $mname:expr => $alias:expr ; $( $rest:tt )* ) => ({
+    $cls.add_overridable_method_as($alias, $mname, $is_const, function!($rtype) );
+    class_overridables!($cls, $is_const, $( $rest )* )
+  });
+
+  ($cls:expr , $is_const:expr , $rtype:expr , $mname:expr => $alias:expr, $( $args:expr ),+ ; $( $rest:tt )* ) => ({
+    $cls.add_overridable_method_as($alias, $mname, $is_const, function!($rtype, $( $args ),+ ) );
+    class_overridables!($cls, $is_const, $( $rest )* )
+  });
 }
 
 #[macro_export]
@@ -375,15 +1985,42 @@ macro_rules! class_ctors {
   });
 }
 
+/// methods and constructors are kept in declaration order so that the
+/// generated code is the same from one build to the next.
 pub struct Class {
-  methods: HashMap<&'static [u8], Method>,
-  ctors: HashMap<&'static [u8], FunctionArgs>
+  methods: Vec<(&'static [u8], Method)>,
+  ctors: Vec<(&'static [u8], FunctionArgs)>,
+  static_methods: Vec<(&'static [u8], Method)>,
+  functions: Vec<(&'static [u8], Method)>,
+  bases: Vec<ClassPath<'static>>,
+  overridables: Vec<(&'static [u8], Method)>,
+  operators: Vec<(&'static [u8], OperatorMethod)>,
+  enums: Vec<(&'static [u8], Vec<&'static [u8]>)>,
+  constants: Vec<(&'static [u8], proto::BasicType)>
 }
 
 pub fn class() -> Class {
   Class {
-    methods: HashMap::new(),
-    ctors:   HashMap::new()
+    methods:        Vec::new(),
+    ctors:          Vec::new(),
+    static_methods: Vec::new(),
+    functions:      Vec::new(),
+    bases:          Vec::new(),
+    overridables:   Vec::new(),
+    operators:      Vec::new(),
+    enums:          Vec::new(),
+    constants:      Vec::new()
+  }
+}
+
+/// adds `(name, value)` to `list`, replacing an existing entry of the
+/// same name in place.
+fn insert_ordered<V>(list: &mut Vec<(&'static [u8], V)>,
+                     name: &'static [u8],
+                     value: V) {
+  match list.iter().position(|&(n, _)| n == name) {
+    Some(i) => list[i] = (name, value),
+    None    => list.push((name, value))
   }
 }
 
@@ -399,58 +2036,516 @@ fn c_path_ns_part(ns_part: &[u8], out: &mut Write) -> Result<()> {
   Ok(())
 }
 
-fn generate_c_path(namespace: &Vec<&[u8]>,
-                   name: &[u8],
-                   out: &mut Write) -> Result<()> {
+/// a C++ class name: its namespace, its name, any template arguments
+/// and the names of classes nested inside it, for example
+/// `CryptoPP::CBC_Mode<AES>::Encryption`.
+#[derive(Clone)]
+pub struct ClassPath<'a> {
+  pub namespace:     Vec<&'a [u8]>,
+  pub name:          &'a [u8],
+  pub template_args: Vec<ClassPath<'a>>,
+  pub nested:        Vec<&'a [u8]>,
+}
+
+pub fn class_path<'a>(namespace: Vec<&'a [u8]>, name: &'a [u8]) -> ClassPath<'a> {
+  ClassPath {
+    namespace: namespace,
+    name: name,
+    template_args: vec![],
+    nested: vec![],
+  }
+}
+
+impl<'a> ClassPath<'a> {
+  /// appends a template argument.
+  pub fn template_arg(mut self, arg: ClassPath<'a>) -> ClassPath<'a> {
+    self.template_args.push(arg);
+    self
+  }
+
+  /// appends the name of a class nested in this one.
+  pub fn nested(mut self, name: &'a [u8]) -> ClassPath<'a> {
+    self.nested.push(name);
+    self
+  }
+
+  /// writes the identifier used in the names of the C shims for this
+  /// class, mangled so that no two classes share one: every `_` inside
+  /// a name is doubled, then `::`, `<`, `,` and `>` become `_S`, `_L`,
+  /// `_C` and `_R`. `CBC_Mode<AES>::Encryption` becomes
+  /// `CBC__Mode_LAES_R_SEncryption` and `CryptoPP::SHA3_256` becomes
+  /// `CryptoPP_SSHA3__256`.
+  pub fn generate_c_path(&self, out: &mut Write) -> Result<()> {
+    for ns_part in self.namespace.iter() {
+      try!(c_path_ns_part(ns_part, out));
+      try!(out.write_all(b"_S"));
+    }
+    try!(c_path_ns_part(self.name, out));
+
+    if self.template_args.len() > 0 {
+      try!(out.write_all(b"_L"));
+      for (i, arg) in self.template_args.iter().enumerate() {
+        if i > 0 {
+          try!(out.write_all(b"_C"));
+        }
+        try!(arg.generate_c_path(out));
+      }
+      try!(out.write_all(b"_R"));
+    }
+
+    for nested in self.nested.iter() {
+      try!(out.write_all(b"_S"));
+      try!(c_path_ns_part(nested, out));
+    }
+
+    Ok(())
+  }
+
+  pub fn generate_cpp_path(&self, out: &mut Write) -> Result<()> {
+    for ns_part in self.namespace.iter() {
+      try!(out.write_all(ns_part));
+      try!(out.write_all(b"::"));
+    }
+    try!(out.write_all(self.name));
+
+    if let Some(last) = self.template_args.last() {
+      try!(out.write_all(b"<"));
+      for (i, arg) in self.template_args.iter().enumerate() {
+        if i > 0 {
+          try!(out.write_all(b", "));
+        }
+        try!(arg.generate_cpp_path(out));
+      }
+
+      // avoid `>>`, which pre-C++11 compilers read as a shift
+      if last.template_args.len() > 0 && last.nested.len() < 1 {
+        try!(out.write_all(b" "));
+      }
+      try!(out.write_all(b">"));
+    }
+
+    for nested in self.nested.iter() {
+      try!(out.write_all(b"::"));
+      try!(out.write_all(nested));
+    }
+
+    Ok(())
+  }
+}
+
+/// writes `upcast_<derived>_<base>`, the name of the shim converting a
+/// pointer to `derived` into a pointer to its `base` subobject.
+fn generate_upcast_name(derived: &ClassPath,
+                        base: &ClassPath,
+                        out: &mut Write) -> Result<()> {
+  try!(out.write_all(b"upcast_"));
+  try!(derived.generate_c_path(out));
+  try!(out.write_all(b"_"));
+  base.generate_c_path(out)
+}
+
+/// writes the `_`-separated namespace and name of a namespace
+/// function. its `fn_` shims never meet the C paths of classes.
+fn generate_plain_c_path(namespace: &Vec<&[u8]>,
+                            name: &[u8],
+                            out: &mut Write) -> Result<()> {
   for ns_part in namespace.iter() {
     try!(c_path_ns_part(ns_part, out));
     try!(out.write_all(b"_"));
   }
-  try!(out.write_all(name));
+  try!(out.write_all(name));
+
+  Ok(())
+}
+
+/// the C++ class named by a custom type, if `t` is one.
+fn custom_name(t: &proto::BasicType) -> Option<&'static [u8]> {
+  use proto::BasicType::*;
+
+  match t {
+    &Simple(proto::Custom(name))       |
+    &MutPointer(proto::Custom(name))   |
+    &ConstPointer(proto::Custom(name)) |
+    &MutRef(proto::Custom(name))       |
+    &ConstRef(proto::Custom(name))     |
+    &Owned(proto::Custom(name))        => Some(name),
+    _                                  => None
+  }
+}
+
+/// writes the C path of the class spelled `name` in C++, so that a
+/// custom type and the class binding it share a handle.
+fn generate_c_path_of(name: &[u8], out: &mut Write) -> Result<()> {
+  let name = try!(std::str::from_utf8(name).map_err(|e| {
+    Error::Unexpected(String::from(error::Error::description(&e)))
+  }));
+  let path = try!(spec::parse_class_path(name));
+
+  path.generate_c_path(out)
+}
+
+/// writes `t` as seen from C. classes are opaque `rcpp_*` handles.
+fn generate_c_type(t: &proto::BasicType, out: &mut Write) -> Result<()> {
+  use proto::BasicType::*;
+
+  if t.is_string() {
+    try!(out.write_all(b"rcpp_string"));
+    return Ok(());
+  }
+
+  let (ctype, suffix): (&proto::CType, &[u8]) = match t {
+    &Simple(ref c)       => (c, b""),
+    &MutPointer(ref c)   |
+    &MutRef(ref c)       |
+    &Owned(ref c)        => (c, b"*"),
+    &ConstPointer(ref c) |
+    &ConstRef(ref c)     => (c, b" const*"),
+  };
+
+  match ctype {
+    &proto::Custom(name) => {
+      try!(out.write_all(b"rcpp_"));
+      try!(generate_c_path_of(name, out));
+    },
+    _                    => try!(ctype.generate_c(out))
+  }
+  try!(out.write_all(suffix));
+
+  Ok(())
+}
+
+/// writes a typedef for each handle, guarded so that every class
+/// section of the header can declare the handles it uses.
+fn generate_c_handles(handles: &Vec<Vec<u8>>, out: &mut Write) -> Result<()> {
+  for handle in handles.iter() {
+    try!(out.write_all(b"#ifndef RCPP_HANDLE_"));
+    try!(out.write_all(handle));
+    try!(out.write_all(b"\n#define RCPP_HANDLE_"));
+    try!(out.write_all(handle));
+    try!(out.write_all(b"\ntypedef struct rcpp_"));
+    try!(out.write_all(handle));
+    try!(out.write_all(b" rcpp_"));
+    try!(out.write_all(handle));
+    try!(out.write_all(b";\n#endif\n"));
+  }
+
+  Ok(())
+}
+
+fn generate_c_prototype(ret: &[u8],
+                        shim_name: &[u8],
+                        ctx: &[u8],
+                        args: &FunctionArgs,
+                        out_stream: &mut Write) -> Result<()> {
+  try!(out_stream.write_all(ret));
+  try!(out_stream.write_all(b" "));
+  try!(out_stream.write_all(shim_name));
+  try!(out_stream.write_all(b"("));
+  if ctx.len() > 0 {
+    try!(out_stream.write_all(ctx));
+    try!(out_stream.write_all(b", "));
+  }
+  if args.len() > 0 {
+    try!(args.generate_proto_c(out_stream));
+    try!(out_stream.write_all(b", "));
+  }
+  try!(out_stream.write_all(b"rcpp_exception* exc);\n"));
+
+  Ok(())
+}
+
+/// writes a shim body that returns `callee(args)`, catching exceptions.
+fn generate_cpp_call(callee: &[u8],
+                     function_desc: &Function,
+                     out_stream: &mut Write) -> Result<()> {
+  let mut expr = Vec::new();
+  try!(expr.write_all(callee));
+  try!(expr.write_all(b"("));
+  try!(function_desc.args.generate_apply_cpp(&mut expr));
+  try!(expr.write_all(b")"));
+
+  generate_cpp_expr(&expr, &function_desc.ret, out_stream)
+}
+
+/// writes a shim body that returns the value of `expr` as `ret`.
+fn generate_cpp_expr(expr: &[u8],
+                     ret: &proto::BasicType,
+                     out_stream: &mut Write) -> Result<()> {
+  try!(generate_cpp_try_open(out_stream));
+  let mut default_ret = Vec::new();
+  if !ret.is_void() {
+    try!(out_stream.write_all(b"return "));
+    if ret.is_string() {
+      try!(out_stream.write_all(b"rcpp_to_string("));
+    } else if ret.is_ref() {
+      try!(out_stream.write_all(b"&"));
+    }
+    if let &proto::BasicType::Owned(ref t) = ret {
+      try!(out_stream.write_all(b"new "));
+      try!(t.generate_cpp(out_stream));
+      try!(out_stream.write_all(b"("));
+    }
+
+    try!(default_ret.write_all(b"rcpp_default<"));
+    try!(ret.generate_cpp(&mut default_ret));
+    try!(default_ret.write_all(b">()"));
+  }
+  try!(out_stream.write_all(expr));
+  if ret.is_owned() || ret.is_string() {
+    try!(out_stream.write_all(b")"));
+  }
+  try!(out_stream.write_all(b";"));
+  generate_cpp_try_close(&default_ret, out_stream)
+}
+
+/// writes a shim without a receiver, used for static methods and free
+/// functions.
+fn generate_cpp_function(shim_name: &[u8],
+                         callee: &[u8],
+                         function_desc: &Function,
+                         out_stream: &mut Write) -> Result<()> {
+  try!(out_stream.write_all(b"extern \"C\"\n"));
+
+  try!(function_desc.ret.generate_cpp(out_stream));
+  try!(out_stream.write_all(b" "));
+  try!(out_stream.write_all(shim_name));
+  try!(out_stream.write_all(b"("));
+
+  if function_desc.args.len() > 0 {
+    try!(function_desc.args.generate_proto_cpp(out_stream));
+    try!(out_stream.write_all(b", "));
+  }
+  try!(out_stream.write_all(b"rcpp_exception* exc)"));
+
+  try!(generate_cpp_call(callee, function_desc, out_stream));
+  try!(out_stream.write_all(b"\n\n"));
+
+  Ok(())
+}
+
+fn generate_rs_function(shim_name: &[u8],
+                        function_desc: &Function,
+                        out_stream: &mut Write) -> Result<()> {
+  try!(out_stream.write_all(b"  pub fn "));
+  try!(out_stream.write_all(shim_name));
+  try!(out_stream.write_all(b"("));
+
+  if function_desc.args.len() > 0 {
+    try!(function_desc.args.generate_proto_rs(out_stream));
+    try!(out_stream.write_all(b", "));
+  }
+  try!(out_stream.write_all(b"exc: *mut Exception)"));
+
+  if !function_desc.ret.is_void() {
+    try!(out_stream.write_all(b" -> "));
+    try!(function_desc.ret.generate_rs(out_stream));
+  }
+  try!(out_stream.write_all(b";\n"));
+
+  Ok(())
+}
+
+/// declares a shim taking the object it is called on, `*const` when
+/// `is_const` is set.
+fn generate_rs_method(shim_name: &[u8],
+                      is_const: bool,
+                      function_desc: &Function,
+                      out_stream: &mut Write) -> Result<()> {
+  try!(out_stream.write_all(b"  pub fn "));
+  try!(out_stream.write_all(shim_name));
+  try!(out_stream.write_all(b"("));
+
+  try!(out_stream.write_all(b"ctx: *"));
+  try!(out_stream.write_all(if is_const {
+    b"const "
+  } else {
+    b"mut "
+  }));
+  try!(out_stream.write_all(b"c_void"));
+
+  if function_desc.args.len() > 0 {
+    try!(out_stream.write_all(b", "));
+    try!(function_desc.args.generate_proto_rs(out_stream));
+  }
+
+  try!(out_stream.write_all(b", exc: *mut Exception)"));
+
+  if !function_desc.ret.is_void() {
+    try!(out_stream.write_all(b" -> "));
+    try!(function_desc.ret.generate_rs(out_stream));
+  }
+  try!(out_stream.write_all(b";\n"));
+
+  Ok(())
+}
+
+impl Class {
+  pub fn add_method(&mut self,
+                    name: &'static [u8],
+                    is_const: bool,
+                    function: Function) {
+    self.add_method_as(name, name, is_const, function);
+  }
+
+  /// binds the C++ method `cpp_name` under the exported name `alias`,
+  /// which lets several overloads of one name be bound.
+  pub fn add_method_as(&mut self,
+                       alias: &'static [u8],
+                       cpp_name: &'static [u8],
+                       is_const: bool,
+                       function: Function) {
+    insert_ordered(&mut self.methods, alias, method(cpp_name, function, is_const));
+  }
+
+  pub fn add_constructor(&mut self, name: &'static [u8], args: FunctionArgs) {
+    insert_ordered(&mut self.ctors, name, args);
+  }
+
+  /// declares a base class. a pointer to the class can then be turned
+  /// into one to `base` with a `static_cast`, which adjusts it for
+  /// multiple and virtual inheritance. `base` must be bound too.
+  pub fn add_base(&mut self, base: ClassPath<'static>) {
+    self.bases.push(base);
+  }
+
+  pub fn add_static_method(&mut self, name: &'static [u8], function: Function) {
+    self.add_static_method_as(name, name, function);
+  }
+
+  pub fn add_static_method_as(&mut self,
+                              alias: &'static [u8],
+                              cpp_name: &'static [u8],
+                              function: Function) {
+    insert_ordered(&mut self.static_methods, alias, method(cpp_name, function, false));
+  }
+
+  /// adds a virtual method to be implemented from Rust. a class with
+  /// any of these gets a C++ subclass forwarding them to a Rust trait
+  /// object, see `NamedClass::write_trait`.
+  pub fn add_overridable_method(&mut self,
+                                name: &'static [u8],
+                                is_const: bool,
+                                function: Function) {
+    self.add_overridable_method_as(name, name, is_const, function);
+  }
+
+  pub fn add_overridable_method_as(&mut self,
+                                   alias: &'static [u8],
+                                   cpp_name: &'static [u8],
+                                   is_const: bool,
+                                   function: Function) {
+    insert_ordered(&mut self.overridables, alias, method(cpp_name, function, is_const));
+  }
+
+  /// adds a free function living in the namespace of the class.
+  pub fn add_function(&mut self, name: &'static [u8], function: Function) {
+    self.add_function_as(name, name, function);
+  }
+
+  pub fn add_function_as(&mut self,
+                         alias: &'static [u8],
+                         cpp_name: &'static [u8],
+                         function: Function) {
+    insert_ordered(&mut self.functions, alias, method(cpp_name, function, false));
+  }
 
-  Ok(())
-}
+  /// binds `op`, exported as `op_<class>_<op.name()>`. the
+  /// `PartialOrd` impl of `Lt` needs the one of `Eq`.
+  pub fn add_operator(&mut self, op: Operator, with_trait: bool, function: Function) {
+    self.add_operator_as(op.name(), op, with_trait, function);
+  }
 
-fn generate_cpp_path(namespace: &Vec<&[u8]>,
-                    name: &[u8],
-                    out: &mut Write) -> Result<()> {
-  for ns_part in namespace.iter() {
-    try!(out.write_all(ns_part));
-    try!(out.write_all(b"::"));
+  /// declares the enum `name` of the class and the names of its
+  /// values. the values themselves are read from C++ at build time,
+  /// see `probe`.
+  pub fn add_enum(&mut self, name: &'static [u8], variants: Vec<&'static [u8]>) {
+    insert_ordered(&mut self.enums, name, variants);
   }
-  try!(out.write_all(name));
 
-  Ok(())
-}
+  /// declares the static constant `name` of the class, which must have
+  /// an integer type.
+  pub fn add_constant(&mut self, name: &'static [u8], ctype: proto::BasicType) {
+    insert_ordered(&mut self.constants, name, ctype);
+  }
 
-impl Class {
-  pub fn add_method(&mut self,
-                    name: &'static [u8],
-                    is_const: bool,
-                    function: Function) {
-    self.methods.insert(name, method(function, is_const));
+  /// whether the class declares enums or constants.
+  pub fn has_consts(&self) -> bool {
+    self.enums.len() > 0 || self.constants.len() > 0
   }
 
-  pub fn add_constructor(&mut self, name: &'static [u8], args: FunctionArgs) {
-    self.ctors.insert(name, args);
+  pub fn add_operator_as(&mut self,
+                         alias: &'static [u8],
+                         op: Operator,
+                         with_trait: bool,
+                         function: Function) {
+    insert_ordered(&mut self.operators, alias, OperatorMethod {
+      op: op,
+      func: function,
+      with_trait: with_trait
+    });
   }
 
   pub fn generate_cpp(&self,
-                      namespace: &Vec<&[u8]>,
-                      name: &[u8],
+                      path: &ClassPath,
                       out_stream: &mut Write) -> Result<()> {
-    try!(self.generate_cpp_ctors(namespace, name, out_stream));
-    self.generate_cpp_methods(namespace, name, out_stream)
+    try!(self.generate_cpp_ctors(path, out_stream));
+    try!(self.generate_cpp_upcasts(path, out_stream));
+    try!(self.generate_cpp_trampoline(path, out_stream));
+    try!(self.generate_cpp_methods(path, out_stream));
+    try!(self.generate_cpp_operators(path, out_stream));
+    try!(self.generate_cpp_static_methods(path, out_stream));
+    self.generate_cpp_functions(&path.namespace, out_stream)
+  }
+
+  fn generate_cpp_static_methods(&self,
+                                 path: &ClassPath,
+                                 out_stream: &mut Write) -> Result<()> {
+    for &(method_name, ref method_desc) in self.static_methods.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"smth_"));
+      try!(path.generate_c_path(&mut shim_name));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(method_name));
+
+      let mut callee = Vec::new();
+      try!(path.generate_cpp_path(&mut callee));
+      try!(callee.write_all(b"::"));
+      try!(callee.write_all(method_desc.cpp_name));
+
+      try!(generate_cpp_function(&shim_name, &callee, &method_desc.func, out_stream));
+    }
+
+    Ok(())
+  }
+
+  fn generate_cpp_functions(&self,
+                            namespace: &Vec<&[u8]>,
+                            out_stream: &mut Write) -> Result<()> {
+    for &(function_name, ref method_desc) in self.functions.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"fn_"));
+      try!(generate_plain_c_path(namespace, function_name, &mut shim_name));
+
+      let mut callee = Vec::new();
+      for ns_part in namespace.iter() {
+        try!(callee.write_all(ns_part));
+        try!(callee.write_all(b"::"));
+      }
+      try!(callee.write_all(method_desc.cpp_name));
+
+      try!(generate_cpp_function(&shim_name, &callee, &method_desc.func, out_stream));
+    }
+
+    Ok(())
   }
 
   fn generate_cpp_ctors(&self,
-                        namespace: &Vec<&[u8]>,
-                        name: &[u8],
+                        path: &ClassPath,
                         out_stream: &mut Write) -> Result<()> {
-    for (ctor_name, ctor_args) in self.ctors.iter() {
+    for &(ctor_name, ref ctor_args) in self.ctors.iter() {
       try!(out_stream.write_all(b"extern \"C\"\n"));
 
-      try!(generate_cpp_path(namespace, name, out_stream));
+      try!(path.generate_cpp_path(out_stream));
       try!(out_stream.write_all(b" *"));
 
       try!(out_stream.write_all(b" new_"));
@@ -458,37 +2553,38 @@ impl Class {
         try!(out_stream.write_all(ctor_name));
         try!(out_stream.write_all(b"_"));
       }
-      try!(generate_c_path(namespace, name, out_stream));
+      try!(path.generate_c_path(out_stream));
 
       try!(out_stream.write_all(b"("));
 
       if ctor_args.len() > 0 {
         try!(ctor_args.generate_proto_cpp(out_stream));
+        try!(out_stream.write_all(b", "));
       }
 
-      try!(out_stream.write_all(b")"));
+      try!(out_stream.write_all(b"rcpp_exception* exc)"));
 
-      try!(out_stream.write_all(b" {\n  "));
+      try!(generate_cpp_try_open(out_stream));
       try!(out_stream.write_all(b"return new "));
 
-      try!(generate_cpp_path(namespace, name, out_stream));
+      try!(path.generate_cpp_path(out_stream));
 
       try!(out_stream.write_all(b"("));
 
       try!(ctor_args.generate_apply_cpp(out_stream));
 
-      try!(out_stream.write_all(b");\n"));
-      try!(out_stream.write_all(b"}"));
+      try!(out_stream.write_all(b");"));
+      try!(generate_cpp_try_close(b"NULL", out_stream));
       try!(out_stream.write_all(b"\n\n"));
     }
 
     try!(out_stream.write_all(b"extern \"C\"\n"));
     try!(out_stream.write_all(b"void del_"));
 
-    try!(generate_c_path(namespace, name, out_stream));
+    try!(path.generate_c_path(out_stream));
     try!(out_stream.write_all(b"("));
 
-    try!(generate_cpp_path(namespace, name, out_stream));
+    try!(path.generate_cpp_path(out_stream));
     try!(out_stream.write_all(b"*"));
     try!(out_stream.write_all(b" ctx"));
     try!(out_stream.write_all(b")"));
@@ -497,11 +2593,125 @@ impl Class {
     Ok(())
   }
 
+  fn generate_cpp_upcasts(&self,
+                          path: &ClassPath,
+                          out_stream: &mut Write) -> Result<()> {
+    for base in self.bases.iter() {
+      try!(out_stream.write_all(b"extern \"C\"\n"));
+      try!(base.generate_cpp_path(out_stream));
+      try!(out_stream.write_all(b"* "));
+      try!(generate_upcast_name(path, base, out_stream));
+      try!(out_stream.write_all(b"("));
+      try!(path.generate_cpp_path(out_stream));
+      try!(out_stream.write_all(b"* ctx) {\n  return static_cast<"));
+      try!(base.generate_cpp_path(out_stream));
+      try!(out_stream.write_all(b"*>(ctx);\n}\n\n"));
+    }
+
+    Ok(())
+  }
+
+  /// writes the `rcpp_rust_*` subclass whose overridable methods call
+  /// through a table of function pointers filled in by Rust, and the
+  /// `new_rust_*` shim creating one.
+  fn generate_cpp_trampoline(&self,
+                             path: &ClassPath,
+                             out_stream: &mut Write) -> Result<()> {
+    if self.overridables.len() < 1 {
+      return Ok(());
+    }
+
+    let mut c_path = Vec::new();
+    try!(path.generate_c_path(&mut c_path));
+
+    try!(out_stream.write_all(b"struct rcpp_vtable_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b" {\n"));
+    for &(method_name, ref method_desc) in self.overridables.iter() {
+      try!(check_overridable(method_name, method_desc));
+      let function_desc = &method_desc.func;
+
+      try!(out_stream.write_all(b"  "));
+      try!(function_desc.ret.generate_cpp(out_stream));
+      try!(out_stream.write_all(b" (*"));
+      try!(out_stream.write_all(method_name));
+      try!(out_stream.write_all(b")(void* self, "));
+      if function_desc.args.len() > 0 {
+        try!(function_desc.args.generate_proto_cpp(out_stream));
+        try!(out_stream.write_all(b", "));
+      }
+      try!(out_stream.write_all(b"rcpp_exception* exc);\n"));
+    }
+    try!(out_stream.write_all(b"  void (*drop)(void* self);\n};\n\n"));
+
+    try!(out_stream.write_all(b"class rcpp_rust_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b" : public "));
+    try!(path.generate_cpp_path(out_stream));
+    try!(out_stream.write_all(b" {\npublic:\n  rcpp_rust_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b"(void* self, rcpp_vtable_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b" const* vtable)\n"));
+    try!(out_stream.write_all(b"    : rcpp_self(self), rcpp_vtable(*vtable) {}\n\n  ~rcpp_rust_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b"() {\n    rcpp_vtable.drop(rcpp_self);\n  }\n\n"));
+
+    for &(method_name, ref method_desc) in self.overridables.iter() {
+      let function_desc = &method_desc.func;
+      let has_ret = !function_desc.ret.is_void();
+
+      try!(out_stream.write_all(b"  "));
+      try!(function_desc.ret.generate_cpp(out_stream));
+      try!(out_stream.write_all(b" "));
+      try!(out_stream.write_all(method_desc.cpp_name));
+      try!(out_stream.write_all(b"("));
+      try!(function_desc.args.generate_proto_cpp(out_stream));
+      try!(out_stream.write_all(if method_desc.is_const { &b") const {\n"[..] } else { &b") {\n"[..] }));
+
+      try!(out_stream.write_all(b"    rcpp_exception exc = { RCPP_EXC_NONE, NULL };\n    "));
+      if has_ret {
+        try!(function_desc.ret.generate_cpp(out_stream));
+        try!(out_stream.write_all(b" ret = "));
+      }
+      try!(out_stream.write_all(b"rcpp_vtable."));
+      try!(out_stream.write_all(method_name));
+      try!(out_stream.write_all(b"(rcpp_self, "));
+      for i in 0..function_desc.args.len() {
+        try!(write!(out_stream, "arg{}, ", i));
+      }
+      try!(out_stream.write_all(b"&exc);\n    rcpp_rethrow(&exc);\n"));
+      if has_ret {
+        try!(out_stream.write_all(b"    return ret;\n"));
+      }
+      try!(out_stream.write_all(b"  }\n\n"));
+    }
+
+    try!(out_stream.write_all(b"private:\n  void* rcpp_self;\n  rcpp_vtable_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b" rcpp_vtable;\n};\n\n"));
+
+    try!(out_stream.write_all(b"extern \"C\"\n"));
+    try!(path.generate_cpp_path(out_stream));
+    try!(out_stream.write_all(b"* new_rust_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b"(void* self, rcpp_vtable_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b" const* vtable, rcpp_exception* exc)"));
+    try!(generate_cpp_try_open(out_stream));
+    try!(out_stream.write_all(b"return new rcpp_rust_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b"(self, vtable);"));
+    try!(generate_cpp_try_close(b"NULL", out_stream));
+    try!(out_stream.write_all(b"\n\n"));
+
+    Ok(())
+  }
+
   fn generate_cpp_methods(&self,
-                          namespace: &Vec<&[u8]>,
-                          name: &[u8],
+                          path: &ClassPath,
                           out_stream: &mut Write) -> Result<()> {
-    for (method_name, method_desc) in self.methods.iter() {
+    for &(method_name, ref method_desc) in self.methods.iter() {
       let function_desc = &method_desc.func;
 
       try!(out_stream.write_all(b"extern \"C\"\n"));
@@ -509,12 +2719,12 @@ impl Class {
       try!(function_desc.ret.generate_cpp(out_stream));
 
       try!(out_stream.write_all(b" mth_"));
-      try!(generate_c_path(namespace, name, out_stream));
+      try!(path.generate_c_path(out_stream));
       try!(out_stream.write_all(b"_"));
       try!(out_stream.write_all(method_name));
       try!(out_stream.write_all(b"("));
 
-      try!(generate_cpp_path(namespace, name, out_stream));
+      try!(path.generate_cpp_path(out_stream));
       if method_desc.is_const {
         try!(out_stream.write_all(b" const"));
       }
@@ -525,20 +2735,54 @@ impl Class {
         try!(out_stream.write_all(b", "));
         try!(function_desc.args.generate_proto_cpp(out_stream));
       }
-      try!(out_stream.write_all(b")"));
+      try!(out_stream.write_all(b", rcpp_exception* exc)"));
 
-      try!(out_stream.write_all(b" {\n  "));
-      if !function_desc.ret.is_void() {
-        try!(out_stream.write_all(b"return "));
-      }
-      try!(out_stream.write_all(b"ctx->"));
-      try!(out_stream.write_all(method_name));
+      let mut callee = Vec::new();
+      try!(callee.write_all(b"ctx->"));
+      try!(callee.write_all(method_desc.cpp_name));
+
+      try!(generate_cpp_call(&callee, function_desc, out_stream));
+      try!(out_stream.write_all(b"\n\n"));
+    }
+
+    Ok(())
+  }
+
+  fn generate_cpp_operators(&self,
+                            path: &ClassPath,
+                            out_stream: &mut Write) -> Result<()> {
+    for &(op_name, ref op_desc) in self.operators.iter() {
+      try!(check_operator(op_name, op_desc));
+      let function_desc = &op_desc.func;
+
+      try!(out_stream.write_all(b"extern \"C\"\n"));
+      try!(function_desc.ret.generate_cpp(out_stream));
+      try!(out_stream.write_all(b" op_"));
+      try!(path.generate_c_path(out_stream));
+      try!(out_stream.write_all(b"_"));
+      try!(out_stream.write_all(op_name));
       try!(out_stream.write_all(b"("));
+      try!(path.generate_cpp_path(out_stream));
+      if !op_desc.op.is_assign() {
+        try!(out_stream.write_all(b" const"));
+      }
+      try!(out_stream.write_all(b"* ctx"));
+      if function_desc.args.len() > 0 {
+        try!(out_stream.write_all(b", "));
+        try!(function_desc.args.generate_proto_cpp(out_stream));
+      }
+      try!(out_stream.write_all(b", rcpp_exception* exc)"));
 
-      try!(function_desc.args.generate_apply_cpp(out_stream));
+      let mut expr = Vec::new();
+      if op_desc.op == Operator::Neg {
+        try!(expr.write_all(b"-*ctx"));
+      } else {
+        try!(expr.write_all(b"*ctx "));
+        try!(expr.write_all(op_desc.op.cpp_token()));
+        try!(function_desc.args.generate_apply_cpp(&mut expr));
+      }
 
-      try!(out_stream.write_all(b");\n"));
-      try!(out_stream.write_all(b"}"));
+      try!(generate_cpp_expr(&expr, &function_desc.ret, out_stream));
       try!(out_stream.write_all(b"\n\n"));
     }
 
@@ -546,15 +2790,31 @@ impl Class {
   }
 
   pub fn generate_rs(&self,
-                     namespace: &Vec<&[u8]>,
-                     name: &[u8],
+                     path: &ClassPath,
                      out_stream: &mut Write) -> Result<()> {
+    // an opaque type standing for the class, names it in `Upcast`
+    try!(out_stream.write_all(b"#[allow(non_camel_case_types)]\npub enum "));
+    try!(path.generate_c_path(out_stream));
+    try!(out_stream.write_all(b" {}\n"));
+    try!(self.generate_rs_vtable(path, out_stream));
+
     try!(out_stream.write_all(
       b"extern {\n"
     ));
 
-    try!(self.generate_rs_methods(namespace, name, out_stream));
-    try!(self.generate_rs_ctors(namespace, name, out_stream));
+    try!(self.generate_rs_methods(path, out_stream));
+    try!(self.generate_rs_operators(path, out_stream));
+    try!(self.generate_rs_ctors(path, out_stream));
+    try!(self.generate_rs_upcasts(path, out_stream));
+    if self.overridables.len() > 0 {
+      try!(out_stream.write_all(b"  pub fn new_rust_"));
+      try!(path.generate_c_path(out_stream));
+      try!(out_stream.write_all(b"(this: *mut c_void, vtable: *const vtable_"));
+      try!(path.generate_c_path(out_stream));
+      try!(out_stream.write_all(b", exc: *mut Exception) -> *mut c_void;\n"));
+    }
+    try!(self.generate_rs_static_methods(path, out_stream));
+    try!(self.generate_rs_functions(&path.namespace, out_stream));
 
     try!(out_stream.write_all(b"}\n"));
 
@@ -562,101 +2822,373 @@ impl Class {
   }
 
   fn generate_rs_ctors(&self,
-                       namespace: &Vec<&[u8]>,
-                       name: &[u8],
+                       path: &ClassPath,
                        out_stream: &mut Write) -> Result<()> {
-    if self.ctors.len() < 1 {
-      return Ok(())
-    }
-
-    for (ctor_name, ctor_args) in self.ctors.iter() {
+    for &(ctor_name, ref ctor_args) in self.ctors.iter() {
       try!(out_stream.write_all(b"  pub fn new_"));
       if ctor_name.len() > 0 {
         try!(out_stream.write_all(ctor_name));
         try!(out_stream.write_all(b"_"));
       }
-      try!(generate_c_path(namespace, name, out_stream));
+      try!(path.generate_c_path(out_stream));
 
       try!(out_stream.write_all(b"("));
 
       if ctor_args.len() > 0 {
         try!(ctor_args.generate_proto_rs(out_stream));
+        try!(out_stream.write_all(b", "));
       }
 
-      try!(out_stream.write_all(b")"));
+      try!(out_stream.write_all(b"exc: *mut Exception)"));
       try!(out_stream.write_all(b" -> *mut c_void"));
       try!(out_stream.write_all(b";\n"));
     }
 
     try!(out_stream.write_all(b"  pub fn del_"));
-    try!(generate_c_path(namespace, name, out_stream));
+    try!(path.generate_c_path(out_stream));
     try!(out_stream.write_all(b"(ctx: *mut c_void);\n"));
 
     Ok(())
   }
 
+  fn generate_rs_vtable(&self,
+                        path: &ClassPath,
+                        out_stream: &mut Write) -> Result<()> {
+    if self.overridables.len() < 1 {
+      return Ok(());
+    }
+
+    try!(out_stream.write_all(b"#[repr(C)]\n#[allow(non_snake_case, non_camel_case_types)]\n"));
+    try!(out_stream.write_all(b"pub struct vtable_"));
+    try!(path.generate_c_path(out_stream));
+    try!(out_stream.write_all(b" {\n"));
+    for &(method_name, ref method_desc) in self.overridables.iter() {
+      let function_desc = &method_desc.func;
+
+      try!(out_stream.write_all(b"  pub "));
+      try!(out_stream.write_all(method_name));
+      try!(out_stream.write_all(b": extern \"C\" fn(this: *mut c_void, "));
+      if function_desc.args.len() > 0 {
+        try!(function_desc.args.generate_proto_rs(out_stream));
+        try!(out_stream.write_all(b", "));
+      }
+      try!(out_stream.write_all(b"exc: *mut Exception)"));
+      if !function_desc.ret.is_void() {
+        try!(out_stream.write_all(b" -> "));
+        try!(function_desc.ret.generate_rs(out_stream));
+      }
+      try!(out_stream.write_all(b",\n"));
+    }
+    try!(out_stream.write_all(b"  pub drop: extern \"C\" fn(this: *mut c_void)\n}\n"));
+
+    Ok(())
+  }
+
+  fn generate_rs_upcasts(&self,
+                         path: &ClassPath,
+                         out_stream: &mut Write) -> Result<()> {
+    for base in self.bases.iter() {
+      try!(out_stream.write_all(b"  pub fn "));
+      try!(generate_upcast_name(path, base, out_stream));
+      try!(out_stream.write_all(b"(ctx: *mut c_void) -> *mut c_void;\n"));
+    }
+
+    Ok(())
+  }
+
   fn generate_rs_methods(&self,
-                         namespace: &Vec<&[u8]>,
-                         name: &[u8],
+                         path: &ClassPath,
                          out_stream: &mut Write) -> Result<()> {
-    for (method_name, method_desc) in self.methods.iter() {
-      let function_desc = &method_desc.func;
+    for &(method_name, ref method_desc) in self.methods.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"mth_"));
+      try!(path.generate_c_path(&mut shim_name));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(method_name));
+
+      try!(generate_rs_method(&shim_name, method_desc.is_const, &method_desc.func, out_stream));
+    }
 
-      try!(out_stream.write_all(b"  pub fn mth_"));
+    Ok(())
+  }
 
-      try!(generate_c_path(namespace, name, out_stream));
-      try!(out_stream.write_all(b"_"));
-      try!(out_stream.write_all(method_name));
-      try!(out_stream.write_all(b"("));
+  fn generate_rs_operators(&self,
+                           path: &ClassPath,
+                           out_stream: &mut Write) -> Result<()> {
+    for &(op_name, ref op_desc) in self.operators.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"op_"));
+      try!(path.generate_c_path(&mut shim_name));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(op_name));
+
+      try!(generate_rs_method(&shim_name, !op_desc.op.is_assign(), &op_desc.func, out_stream));
+    }
 
-      try!(out_stream.write_all(b"ctx: *"));
-      try!(out_stream.write_all(if method_desc.is_const {
-        b"const "
-      } else {
-        b"mut "
-      }));
-      try!(out_stream.write_all(b"c_void"));
+    Ok(())
+  }
 
-      let arg_len = function_desc.args.len();
-      if arg_len > 0 {
-        try!(out_stream.write_all(b", "));
-        try!(function_desc.args.generate_proto_rs(out_stream));
+  fn generate_rs_static_methods(&self,
+                                path: &ClassPath,
+                                out_stream: &mut Write) -> Result<()> {
+    for &(method_name, ref method_desc) in self.static_methods.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"smth_"));
+      try!(path.generate_c_path(&mut shim_name));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(method_name));
+
+      try!(generate_rs_function(&shim_name, &method_desc.func, out_stream));
+    }
+
+    Ok(())
+  }
+
+  fn generate_rs_functions(&self,
+                           namespace: &Vec<&[u8]>,
+                           out_stream: &mut Write) -> Result<()> {
+    for &(function_name, ref method_desc) in self.functions.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"fn_"));
+      try!(generate_plain_c_path(namespace, function_name, &mut shim_name));
+
+      try!(generate_rs_function(&shim_name, &method_desc.func, out_stream));
+    }
+
+    Ok(())
+  }
+
+  /// the C handle of the class at `path`, of its bases and of every
+  /// class its bindings take or return.
+  fn c_handles(&self, path: Option<&ClassPath>) -> Result<Vec<Vec<u8>>> {
+    let mut handles = Vec::new();
+    {
+      let mut add = |handle: Vec<u8>| {
+        if !handles.contains(&handle) {
+          handles.push(handle);
+        }
+      };
+
+      if let Some(path) = path {
+        let mut handle = Vec::new();
+        try!(path.generate_c_path(&mut handle));
+        add(handle);
+      }
+      for base in self.bases.iter() {
+        let mut handle = Vec::new();
+        try!(base.generate_c_path(&mut handle));
+        add(handle);
       }
 
-      try!(out_stream.write_all(b")"));
+      let all_args = self.ctors.iter().map(|&(_, ref args)| args);
+      let methods = self.methods.iter()
+        .chain(self.overridables.iter())
+        .chain(self.static_methods.iter())
+        .chain(self.functions.iter());
+      let mut types: Vec<&proto::BasicType> = all_args.flat_map(|args| args.as_slice().iter()).collect();
+      for &(_, ref method_desc) in methods {
+        types.push(&method_desc.func.ret);
+        types.extend(method_desc.func.args.as_slice().iter());
+      }
+      for &(_, ref op_desc) in self.operators.iter() {
+        types.push(&op_desc.func.ret);
+        types.extend(op_desc.func.args.as_slice().iter());
+      }
 
-      if !function_desc.ret.is_void() {
-        try!(out_stream.write_all(b" -> "));
-        try!(function_desc.ret.generate_rs(out_stream));
+      for t in types.into_iter() {
+        if let Some(name) = custom_name(t) {
+          let mut handle = Vec::new();
+          try!(generate_c_path_of(name, &mut handle));
+          add(handle);
+        }
+      }
+    }
+
+    Ok(handles)
+  }
+
+  /// writes the prototypes of the shims of the class at `path`, in the
+  /// order of `generate_cpp`.
+  pub fn generate_c_header(&self,
+                           path: &ClassPath,
+                           out_stream: &mut Write) -> Result<()> {
+    let mut c_path = Vec::new();
+    try!(path.generate_c_path(&mut c_path));
+    let mut handle = Vec::new();
+    try!(handle.write_all(b"rcpp_"));
+    try!(handle.write_all(&c_path));
+
+    try!(out_stream.write_all(b"/* "));
+    try!(path.generate_cpp_path(out_stream));
+    try!(out_stream.write_all(b" */\n"));
+    try!(generate_c_handles(&try!(self.c_handles(Some(path))), out_stream));
+
+    let mut ret = handle.clone();
+    try!(ret.write_all(b"*"));
+    for &(ctor_name, ref ctor_args) in self.ctors.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"new_"));
+      if ctor_name.len() > 0 {
+        try!(shim_name.write_all(ctor_name));
+        try!(shim_name.write_all(b"_"));
+      }
+      try!(shim_name.write_all(&c_path));
+
+      try!(generate_c_prototype(&ret, &shim_name, b"", ctor_args, out_stream));
+    }
+
+    try!(out_stream.write_all(b"void del_"));
+    try!(out_stream.write_all(&c_path));
+    try!(out_stream.write_all(b"("));
+    try!(out_stream.write_all(&handle));
+    try!(out_stream.write_all(b"* ctx);\n"));
+
+    for base in self.bases.iter() {
+      try!(out_stream.write_all(b"rcpp_"));
+      try!(base.generate_c_path(out_stream));
+      try!(out_stream.write_all(b"* "));
+      try!(generate_upcast_name(path, base, out_stream));
+      try!(out_stream.write_all(b"("));
+      try!(out_stream.write_all(&handle));
+      try!(out_stream.write_all(b"* ctx);\n"));
+    }
+
+    if self.overridables.len() > 0 {
+      try!(out_stream.write_all(b"typedef struct rcpp_vtable_"));
+      try!(out_stream.write_all(&c_path));
+      try!(out_stream.write_all(b" {\n"));
+      for &(method_name, ref method_desc) in self.overridables.iter() {
+        try!(check_overridable(method_name, method_desc));
+        let function_desc = &method_desc.func;
+
+        try!(out_stream.write_all(b"  "));
+        try!(generate_c_type(&function_desc.ret, out_stream));
+        try!(out_stream.write_all(b" (*"));
+        try!(out_stream.write_all(method_name));
+        try!(out_stream.write_all(b")(void* self, "));
+        if function_desc.args.len() > 0 {
+          try!(function_desc.args.generate_proto_c(out_stream));
+          try!(out_stream.write_all(b", "));
+        }
+        try!(out_stream.write_all(b"rcpp_exception* exc);\n"));
       }
+      try!(out_stream.write_all(b"  void (*drop)(void* self);\n} rcpp_vtable_"));
+      try!(out_stream.write_all(&c_path));
       try!(out_stream.write_all(b";\n"));
+
+      try!(out_stream.write_all(&ret));
+      try!(out_stream.write_all(b" new_rust_"));
+      try!(out_stream.write_all(&c_path));
+      try!(out_stream.write_all(b"(void* self, rcpp_vtable_"));
+      try!(out_stream.write_all(&c_path));
+      try!(out_stream.write_all(b" const* vtable, rcpp_exception* exc);\n"));
+    }
+
+    for &(method_name, ref method_desc) in self.methods.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"mth_"));
+      try!(shim_name.write_all(&c_path));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(method_name));
+
+      let mut ctx = handle.clone();
+      if method_desc.is_const {
+        try!(ctx.write_all(b" const"));
+      }
+      try!(ctx.write_all(b"* ctx"));
+
+      let mut ret = Vec::new();
+      try!(generate_c_type(&method_desc.func.ret, &mut ret));
+      try!(generate_c_prototype(&ret, &shim_name, &ctx, &method_desc.func.args, out_stream));
+    }
+
+    for &(op_name, ref op_desc) in self.operators.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"op_"));
+      try!(shim_name.write_all(&c_path));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(op_name));
+
+      let mut ctx = handle.clone();
+      if !op_desc.op.is_assign() {
+        try!(ctx.write_all(b" const"));
+      }
+      try!(ctx.write_all(b"* ctx"));
+
+      let mut ret = Vec::new();
+      try!(generate_c_type(&op_desc.func.ret, &mut ret));
+      try!(generate_c_prototype(&ret, &shim_name, &ctx, &op_desc.func.args, out_stream));
+    }
+
+    for &(method_name, ref method_desc) in self.static_methods.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"smth_"));
+      try!(shim_name.write_all(&c_path));
+      try!(shim_name.write_all(b"_"));
+      try!(shim_name.write_all(method_name));
+
+      let mut ret = Vec::new();
+      try!(generate_c_type(&method_desc.func.ret, &mut ret));
+      try!(generate_c_prototype(&ret, &shim_name, b"", &method_desc.func.args, out_stream));
+    }
+
+    try!(self.generate_c_header_functions(&path.namespace, out_stream));
+    try!(out_stream.write_all(b"\n"));
+
+    Ok(())
+  }
+
+  fn generate_c_header_functions(&self,
+                                 namespace: &Vec<&[u8]>,
+                                 out_stream: &mut Write) -> Result<()> {
+    for &(function_name, ref method_desc) in self.functions.iter() {
+      let mut shim_name = Vec::new();
+      try!(shim_name.write_all(b"fn_"));
+      try!(generate_plain_c_path(namespace, function_name, &mut shim_name));
+
+      let mut ret = Vec::new();
+      try!(generate_c_type(&method_desc.func.ret, &mut ret));
+      try!(generate_c_prototype(&ret, &shim_name, b"", &method_desc.func.args, out_stream));
+    }
+
+    Ok(())
+  }
+
+  /// see the free `check_strings`.
+  fn check_strings(&self) -> Result<()> {
+    let void = proto::void();
+    for &(name, ref args) in self.ctors.iter() {
+      try!(check_strings(name, &void, args));
+    }
+    for &(name, ref method_desc) in self.methods.iter()
+                                      .chain(self.static_methods.iter())
+                                      .chain(self.functions.iter())
+                                      .chain(self.overridables.iter()) {
+      try!(check_strings(name, &method_desc.func.ret, &method_desc.func.args));
+    }
+    for &(name, ref op_desc) in self.operators.iter() {
+      try!(check_strings(name, &op_desc.func.ret, &op_desc.func.args));
     }
 
     Ok(())
   }
 
   pub fn generate(&self,
-              namespace: &Vec<&[u8]>,
-              name: &[u8],
-              cpp_stream: &mut Write,
-              rs_stream: &mut Write) -> Result<()> {
-    try!(self.generate_cpp(&namespace, name, cpp_stream));
-    self.generate_rs(&namespace, name, rs_stream)
+                  path: &ClassPath,
+                  cpp_stream: &mut Write,
+                  rs_stream: &mut Write,
+                  c_header_stream: &mut Write) -> Result<()> {
+    try!(self.check_strings());
+    try!(self.generate_cpp(path, cpp_stream));
+    try!(self.generate_rs(path, rs_stream));
+    self.generate_c_header(path, c_header_stream)
   }
 }
 
 #[macro_export]
 macro_rules! function_args {
-  () => (
-    $crate::FunctionArgs::None
-  );
-
-  ($arg1:expr) => (
-    $crate::FunctionArgs::Args1([$arg1])
-  );
-
-  ($arg1:expr, $arg2:expr) => (
-    $crate::FunctionArgs::Args2([$arg1, $arg2])
+  ( $( $arg:expr ),* ) => (
+    $crate::FunctionArgs::new(vec![ $( $arg ),* ])
   );
 }
 
@@ -689,7 +3221,12 @@ pub mod proto {
     MutPointer(CType),
     ConstPointer(CType),
     MutRef(CType),
-    ConstRef(CType)
+    ConstRef(CType),
+    /// an object passed or returned by value. a returned object is
+    /// copied to the heap by the shim and handed over as a pointer
+    /// to be released with the matching `del_*` shim. an argument is
+    /// passed as a pointer to the object to copy.
+    Owned(CType)
   }
 
   impl BasicType {
@@ -711,13 +3248,39 @@ pub mod proto {
       }
     }
 
+    pub fn is_owned(&self) -> bool {
+      if let &BasicType::Owned(_) = self {
+        return true;
+      }
+
+      false
+    }
+
+    /// whether the type is a `std::string` crossing the shim as an
+    /// `rcpp_string`.
+    pub fn is_string(&self) -> bool {
+      use self::BasicType::*;
+      match self {
+        &Simple(CType::StdString)    |
+        &Simple(CType::Utf8String)   |
+        &ConstRef(CType::StdString)  |
+        &ConstRef(CType::Utf8String) => true,
+        _                            => false
+      }
+    }
+
     pub fn generate_cpp(&self, out_stream: &mut Write) -> io::Result<()> {
       use self::BasicType::*;
 
+      if self.is_string() {
+        return out_stream.write_all(b"rcpp_string");
+      }
+
       match self {
         &Simple(ref t)       => t.generate_cpp(out_stream),
         &MutPointer(ref t)   |
-        &MutRef(ref t)       => {
+        &MutRef(ref t)       |
+        &Owned(ref t)        => {
           try!(t.generate_cpp(out_stream));
           out_stream.write_all(b"*")
         },
@@ -732,10 +3295,15 @@ pub mod proto {
     pub fn generate_rs(&self, out_stream: &mut Write) -> io::Result<()> {
       use self::BasicType::*;
 
+      if self.is_string() {
+        return out_stream.write_all(b"CppString");
+      }
+
       match self {
         &Simple(ref t)       => t.generate_rs(out_stream),
         &MutPointer(ref t)   |
-        &MutRef(ref t)       => {
+        &MutRef(ref t)       |
+        &Owned(ref t)        => {
           try!(out_stream.write_all(b"*mut "));
           t.generate_rs(out_stream)
         },
@@ -750,11 +3318,31 @@ pub mod proto {
   
   pub enum CType {
     Void,
+    Bool,
+    Char,
     UChar,
+    Byte,
+    Int,
     UInt,
-    SizeT,
     Long,
-    Custom(&'static [u8])
+    LongLong,
+    SizeT,
+    Word,
+    Word32,
+    Word64,
+    LWord,
+    Double,
+    Custom(&'static [u8]),
+    /// a C++ enum, passed as an `int`. the safe wrappers take the Rust
+    /// enum of the same name, see `Class::add_enum`.
+    Enum(&'static [u8]),
+    /// a `std::string`, copied to and from an `rcpp_string`. only a
+    /// value or a `const` reference can be bound. the safe wrappers
+    /// take a `&[u8]` and return a `Vec<u8>`.
+    StdString,
+    /// a `std::string` holding text, as `StdString` but taking a `&str`
+    /// and returning a `String`.
+    Utf8String
   }
 
   pub use self::CType::*;
@@ -765,24 +3353,79 @@ pub mod proto {
 
       out_stream.write_all(match self {
         &Void          => b"void",
+        &Bool          => b"bool",
+        &Char          => b"char",
         &UChar         => b"unsigned char",
-        &SizeT         => b"size_t",
+        &Byte          => b"byte",
+        &Int           => b"int",
         &UInt          => b"unsigned int",
         &Long          => b"long",
-        &Custom(ref s) => s,
+        &LongLong      => b"signed long long",
+        &SizeT         => b"size_t",
+        &Word          => b"word",
+        &Word32        => b"word32",
+        &Word64        => b"word64",
+        &LWord         => b"lword",
+        &Double        => b"double",
+        &Custom(ref s) |
+        &Enum(ref s)   => s,
+        &StdString     |
+        &Utf8String    => b"std::string",
       }) // write_all(match...)
     } // generate_cpp
 
+    /// the C spelling of the type, using `stdbool.h` and `stdint.h`.
+    /// a custom type has no C spelling and is written as `void`.
+    pub fn generate_c(&self, out_stream: &mut Write) -> io::Result<()> {
+      use self::CType::*;
+
+      out_stream.write_all(match self {
+        &Void       |
+        &Custom(_)  => b"void",
+        &Enum(_)    => b"int",
+        &StdString  |
+        &Utf8String => b"rcpp_string",
+        &Bool       => b"bool",
+        &Char       => b"char",
+        &UChar      |
+        &Byte       => b"unsigned char",
+        &Int        => b"int",
+        &UInt       => b"unsigned int",
+        &Long       => b"long",
+        &LongLong   => b"long long",
+        &SizeT      |
+        &Word       => b"size_t",
+        &Word32     => b"uint32_t",
+        &Word64     |
+        &LWord      => b"uint64_t",
+        &Double     => b"double",
+      }) // write_all(match...)
+    } // generate_c
+
+    /// `word` is checked to be the size of `size_t` by the C++ prelude.
     pub fn generate_rs(&self, out_stream: &mut Write) -> io::Result<()> {
       use self::CType::*;
 
       out_stream.write_all(match self {
         &Void       |
         &Custom(_)  => b"c_void",
-        &UChar      => b"c_uchar",
-        &SizeT      => b"size_t",
+        &Enum(_)    => b"c_int",
+        &StdString  |
+        &Utf8String => b"CppString",
+        &Bool       => b"bool",
+        &Char       => b"c_char",
+        &UChar      |
+        &Byte       => b"c_uchar",
+        &Int        => b"c_int",
         &UInt       => b"c_uint",
         &Long       => b"c_long",
+        &LongLong   => b"c_longlong",
+        &SizeT      |
+        &Word       => b"size_t",
+        &Word32     => b"u32",
+        &Word64     |
+        &LWord      => b"u64",
+        &Double     => b"c_double",
       }) // write_all(match...)
     } // generate_rs
   }
@@ -791,6 +3434,18 @@ pub mod proto {
     BasicType::Simple(CType::Void)
   }
 
+  pub fn bool() -> BasicType {
+    BasicType::Simple(CType::Bool)
+  }
+
+  pub fn byte() -> BasicType {
+    BasicType::Simple(CType::Byte)
+  }
+
+  pub fn int() -> BasicType {
+    BasicType::Simple(CType::Int)
+  }
+
   pub fn size_t() -> BasicType {
     BasicType::Simple(CType::SizeT)
   }
@@ -803,6 +3458,35 @@ pub mod proto {
     BasicType::Simple(CType::Long)
   }
 
+  pub fn long_long() -> BasicType {
+    BasicType::Simple(CType::LongLong)
+  }
+
+  pub fn word() -> BasicType {
+    BasicType::Simple(CType::Word)
+  }
+
+  pub fn word32() -> BasicType {
+    BasicType::Simple(CType::Word32)
+  }
+
+  pub fn word64() -> BasicType {
+    BasicType::Simple(CType::Word64)
+  }
+
+  pub fn lword() -> BasicType {
+    BasicType::Simple(CType::LWord)
+  }
+
+  pub fn double() -> BasicType {
+    BasicType::Simple(CType::Double)
+  }
+
+  /// a `const char*`.
+  pub fn c_str() -> BasicType {
+    BasicType::ConstPointer(CType::Char)
+  }
+
   pub fn mut_ptr(t: CType) -> BasicType {
     BasicType::MutPointer(t)
   }
@@ -811,13 +3495,28 @@ pub mod proto {
     BasicType::ConstPointer(t)
   }
 
+  pub fn mut_ref(t: CType) -> BasicType {
+    BasicType::MutRef(t)
+  }
+
   pub fn const_ref(t: CType) -> BasicType {
     BasicType::ConstRef(t)
   }
+
+  pub fn owned(t: CType) -> BasicType {
+    BasicType::Owned(t)
+  }
+
+  /// the C++ enum `name`, such as `Integer::Signedness`.
+  pub fn cpp_enum(name: &'static [u8]) -> BasicType {
+    BasicType::Simple(CType::Enum(name))
+  }
+
+  pub fn std_string() -> BasicType {
+    BasicType::Simple(CType::StdString)
+  }
+
+  pub fn utf8_string() -> BasicType {
+    BasicType::Simple(CType::Utf8String)
+  }
 }
-//#define RCPP_NEW(rcpp_t) \
-//  extern "C" \
-//  rcpp_t * new_ ## rcpp_t () { \
-//    return new rcpp_t (); \
-//  }
-//
@@ -0,0 +1,247 @@
+//! the values of bound enums and constants are read from C++ rather
+//! than copied by hand. the build writes a probe program printing
+//! them:
+//!
+//! ```text
+//! <includes>
+//! using namespace CryptoPP;
+//! generate_probe_prelude
+//! NamedClass::generate_probe, for each class
+//! generate_probe_end
+//! ```
+//!
+//! compiles and runs it, reads its output with `Values::parse` and
+//! writes the Rust enums and constants with `NamedClass::generate_consts`.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use proto::{BasicType, CType};
+use {Class, NamedClass, Error, Result};
+
+pub fn generate_probe_prelude<T: Write>(mut stream: T) -> Result<()> {
+  try!(stream.write_all(b"#include <cstdio>\n\nint main() {\n"));
+
+  Ok(())
+}
+
+pub fn generate_probe_end<T: Write>(mut stream: T) -> Result<()> {
+  try!(stream.write_all(b"  return 0;\n}\n"));
+
+  Ok(())
+}
+
+/// the values printed by the probe, one `<class c path> <name> <value>`
+/// line each.
+pub struct Values {
+  values: HashMap<(String, String), String>
+}
+
+impl Values {
+  pub fn parse(output: &str) -> Result<Values> {
+    let mut values = HashMap::new();
+
+    for line in output.lines().filter(|l| l.trim().len() > 0) {
+      let parts: Vec<&str> = line.split_whitespace().collect();
+      if parts.len() != 3 {
+        return Err(Error::Unexpected(format!("bad probe output `{}`", line)));
+      }
+
+      let key = (String::from(parts[0]), String::from(parts[1]));
+      values.insert(key, String::from(parts[2]));
+    }
+
+    Ok(Values { values: values })
+  }
+
+  pub fn get(&self, c_path: &str, name: &[u8]) -> Result<&str> {
+    let name = String::from_utf8_lossy(name).into_owned();
+    match self.values.get(&(String::from(c_path), name.clone())) {
+      Some(value) => Ok(value),
+      None        => Err(Error::Unexpected(format!("no value probed for {}::{}",
+                                                   c_path, name)))
+    }
+  }
+}
+
+/// whether `%llu` rather than `%lld` prints a constant of type `t`.
+fn is_unsigned(t: &CType) -> Option<bool> {
+  match t {
+    &CType::Char     |
+    &CType::Int      |
+    &CType::Long     |
+    &CType::LongLong => Some(false),
+    &CType::UChar    |
+    &CType::Byte     |
+    &CType::UInt     |
+    &CType::SizeT    |
+    &CType::Word     |
+    &CType::Word32   |
+    &CType::Word64   |
+    &CType::LWord    => Some(true),
+    _                => None
+  }
+}
+
+/// `UNSIGNED` becomes `Unsigned` and `W3C_PADDING` becomes `W3cPadding`.
+/// names with lowercase letters only get their first letter uppercased.
+fn rs_variant_name(cpp_name: &[u8]) -> Vec<u8> {
+  let has_lower = cpp_name.iter().any(|&b| b >= b'a' && b <= b'z');
+  let mut name = Vec::new();
+  let mut word_start = true;
+
+  for &b in cpp_name.iter() {
+    if b == b'_' && !has_lower {
+      word_start = true;
+      continue;
+    }
+
+    name.push(match (word_start, has_lower) {
+      (true, _)      => b.to_ascii_uppercase(),
+      (false, false) => b.to_ascii_lowercase(),
+      (false, true)  => b
+    });
+    word_start = false;
+  }
+
+  name
+}
+
+impl<'a, T: Borrow<Class>> NamedClass<'a, T> {
+  /// writes the statements of the probe printing the values of the
+  /// enums and constants of the class.
+  pub fn generate_probe<U: Write>(&self, mut stream: U) -> Result<()> {
+    let cls = self.anon_class.borrow();
+    let c_path = try!(self.c_path());
+    let mut cpp_path = Vec::new();
+    try!(self.path.generate_cpp_path(&mut cpp_path));
+
+    let values = cls.enums.iter()
+      .flat_map(|&(_, ref variants)| variants.iter().map(|&v| (v, true)))
+      .chain(cls.constants.iter().map(|&(name, _)| (name, false)));
+
+    for (name, is_enum) in values {
+      let unsigned = if is_enum {
+        false
+      } else {
+        let ctype = cls.constants.iter().find(|&&(n, _)| n == name).map(|&(_, ref t)| t);
+        match ctype {
+          Some(&BasicType::Simple(ref t)) => match is_unsigned(t) {
+            Some(unsigned) => unsigned,
+            None           => return Err(not_integral(&c_path, name))
+          },
+          _                               => return Err(not_integral(&c_path, name))
+        }
+      };
+
+      try!(write!(stream, "  std::printf(\"%s %s {}\\n\", \"{}\", \"",
+                  if unsigned { "%llu" } else { "%lld" }, c_path));
+      try!(stream.write_all(name));
+      try!(stream.write_all(if unsigned {
+        &b"\", static_cast<unsigned long long>("[..]
+      } else {
+        &b"\", static_cast<long long>("[..]
+      }));
+      try!(stream.write_all(&cpp_path));
+      try!(stream.write_all(b"::"));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b"));\n"));
+    }
+
+    Ok(())
+  }
+
+  /// writes `consts_<c path>.rs`, see `write_consts`.
+  pub fn generate_consts(&self, filepath: &Path, values: &Values) -> Result<()> {
+    let mut fname = try!(self.c_path());
+    fname.insert_str(0, "consts_");
+    fname.push_str(".rs");
+    let mut stream = try!(File::create(filepath.join(fname)));
+
+    self.write_consts(values, &mut stream)
+  }
+
+  /// writes a Rust enum for each enum of the class, its variants named
+  /// in camel case, and a `const` for each constant.
+  pub fn write_consts<U: Write>(&self, values: &Values, mut stream: U) -> Result<()> {
+    let cls = self.anon_class.borrow();
+    let c_path = try!(self.c_path());
+
+    for &(enum_name, ref variants) in cls.enums.iter() {
+      try!(stream.write_all(b"#[derive(Clone, Copy, PartialEq, Eq, Debug)]\npub enum "));
+      try!(stream.write_all(enum_name));
+      try!(stream.write_all(b" {\n"));
+
+      let mut seen = Vec::new();
+      for &variant in variants.iter() {
+        let value = try!(values.get(&c_path, variant));
+        if seen.contains(&value) {
+          return Err(Error::Unexpected(format!("{}::{} has two names for {}", c_path,
+                                               String::from_utf8_lossy(enum_name), value)));
+        }
+        seen.push(value);
+
+        try!(stream.write_all(b"  "));
+        try!(stream.write_all(&rs_variant_name(variant)));
+        try!(write!(stream, " = {},\n", value));
+      }
+      try!(stream.write_all(b"}\n\n"));
+    }
+
+    for &(name, ref ctype) in cls.constants.iter() {
+      let t = match ctype {
+        &BasicType::Simple(ref t) if is_unsigned(t).is_some() => t,
+        _                                                     => return Err(not_integral(&c_path, name))
+      };
+
+      try!(stream.write_all(b"pub const "));
+      try!(stream.write_all(name));
+      try!(stream.write_all(b": "));
+      try!(write_const_type(t, &mut stream));
+      try!(write!(stream, " = {};\n", try!(values.get(&c_path, name))));
+    }
+
+    Ok(())
+  }
+}
+
+fn write_const_type<U: Write>(t: &CType, stream: &mut U) -> Result<()> {
+  let mut rs_type = Vec::new();
+  try!(t.generate_rs(&mut rs_type));
+  if rs_type.starts_with(b"c_") || &rs_type[..] == b"size_t" {
+    try!(stream.write_all(b"::libc::"));
+  }
+  try!(stream.write_all(&rs_type));
+
+  Ok(())
+}
+
+fn not_integral(c_path: &str, name: &[u8]) -> Error {
+  Error::Unexpected(format!("constant {}::{} is not of an integer type",
+                            c_path, String::from_utf8_lossy(name)))
+}
+
+#[cfg(test)]
+mod test {
+  use super::{rs_variant_name, Values};
+
+  #[test]
+  fn variant_names() {
+    assert_eq!(rs_variant_name(b"UNSIGNED"), b"Unsigned");
+    assert_eq!(rs_variant_name(b"W3C_PADDING"), b"W3cPadding");
+    assert_eq!(rs_variant_name(b"HASH_AT_END"), b"HashAtEnd");
+    assert_eq!(rs_variant_name(b"lowerCase"), b"LowerCase");
+  }
+
+  #[test]
+  fn values() {
+    let values = Values::parse("Integer UNSIGNED 0\nInteger SIGNED 1\n\n").unwrap();
+    assert_eq!(values.get("Integer", b"SIGNED").unwrap(), "1");
+    assert!(values.get("Integer", b"DIGESTSIZE").is_err());
+
+    assert!(Values::parse("Integer SIGNED").is_err());
+  }
+}
@@ -0,0 +1,910 @@
+//! bindings described in a TOML file instead of with the `class!`
+//! macros. a spec looks like
+//!
+//! ```toml
+//! headers = ["cryptopp/cryptlib.h", "cryptopp/integer.h"]
+//!
+//! [[class]]
+//! name = "Integer"
+//! struct = "Integer"
+//! bases = []
+//! constructors = [
+//!   { name = "" },
+//!   { name = "from_long", args = ["long"] },
+//! ]
+//! methods = [
+//!   { name = "IsNegative", returns = "bool", const = true },
+//!   { name = "Decode", args = ["const byte*", "size_t"] },
+//! ]
+//! static_methods = [
+//!   { name = "Power2", returns = "Integer", args = ["size_t"] },
+//! ]
+//!
+//! [[namespace]]
+//! name = "CryptoPP"
+//! functions = [
+//!   { name = "Gcd", returns = "Integer", args = ["const Integer&", "const Integer&"] },
+//! ]
+//! ```
+//!
+//! a class may list several C++ names under `names` to bind the same
+//! description for each, like `prototype_class!`. `struct` and `trait`
+//! name the Rust types written by `NamedClass::generate_struct` and
+//! `NamedClass::generate_trait`. methods take an optional `alias` to
+//! export an overload under another name, and `overridable_methods`
+//! declares the virtual methods implemented from Rust.
+//!
+//! `operators` entries name the C++ operator with `op`, such as `"+"`
+//! or `"<<="`, and set `impl = true` to implement the matching Rust
+//! trait too.
+//!
+//! `enums` entries give the `name` of a nested enum and the names of
+//! its `variants`, `constants` entries the `name` and integer `type` of
+//! a static constant. their values come from C++, see `probe`. an enum
+//! argument is spelled `enum Integer::Signedness`.
+//!
+//! a `std::string` taken or returned by value or by `const` reference
+//! is copied to or from a `&[u8]` or `Vec<u8>`, and a `&str` or
+//! `String` when spelled `utf8 std::string`.
+//!
+//! a class may also set a cargo `feature` and the `headers` it needs
+//! besides the spec ones. `Spec::select_features` drops the classes
+//! of disabled features, and their headers with them. `defines` are
+//! macros the headers of the class expect, spelled `NAME` or
+//! `NAME=VALUE`, and are defined before any include. a class or
+//! namespace can also require a Crypto++ version, with `since = "5.6.4"`,
+//! which `Spec::select_version` checks.
+//!
+//! an inline table has to fit on one line.
+//!
+//! types are spelled as in C++, see `parse_type`. a class returned or
+//! passed by value is bound as `proto::owned`.
+
+use std::io::{Read, Write};
+use std::fs::File;
+use std::path::Path;
+
+use toml;
+
+use probe;
+use proto;
+use proto::{BasicType, CType};
+use {Class, ClassPath, Context, Function, FunctionArgs, NamedClass, Namespace,
+     Operator, Error, Result, class, class_path};
+
+/// a class of a spec, bound under each of `paths`.
+pub struct ClassSpec {
+  pub paths:       Vec<ClassPath<'static>>,
+  pub class:       Class,
+  pub struct_name: Option<&'static [u8]>,
+  pub trait_name:  Option<&'static [u8]>,
+  /// the cargo feature the class is bound under, if not always.
+  pub feature:     Option<String>,
+  /// headers the class needs besides the ones of the spec.
+  pub headers:     Vec<String>,
+  /// macros to define before the headers, `NAME` or `NAME=VALUE`.
+  pub defines:     Vec<String>,
+  /// the first Crypto++ version with the class, see `parse_version`.
+  pub since:       Option<u32>,
+}
+
+impl ClassSpec {
+  pub fn named(&self) -> Vec<NamedClass<'static, &Class>> {
+    self.paths.iter().map(|path| {
+      NamedClass::with_path(path.clone(), &self.class)
+    }).collect()
+  }
+}
+
+/// a namespace of a spec, bound when its `feature` is enabled and the
+/// Crypto++ version is at least `since`, as for `ClassSpec`.
+pub struct NamespaceSpec {
+  pub namespace: Namespace<'static, Class>,
+  pub feature:   Option<String>,
+  pub since:     Option<u32>,
+}
+
+pub struct Spec {
+  /// headers to include in the generated C++, in order.
+  pub headers:    Vec<String>,
+  pub classes:    Vec<ClassSpec>,
+  pub namespaces: Vec<NamespaceSpec>,
+}
+
+impl Spec {
+  pub fn new() -> Spec {
+    Spec {
+      headers:    Vec::new(),
+      classes:    Vec::new(),
+      namespaces: Vec::new(),
+    }
+  }
+
+  pub fn load(path: &Path) -> Result<Spec> {
+    let mut src = String::new();
+    try!(try!(File::open(path)).read_to_string(&mut src));
+
+    Spec::parse(&src).map_err(|e| match e {
+      Error::Spec(msg) => Error::Spec(format!("{}: {}", path.display(), msg)),
+      e                => e
+    })
+  }
+
+  pub fn parse(src: &str) -> Result<Spec> {
+    let mut parser = toml::Parser::new(src);
+    let table = match parser.parse() {
+      Some(table) => table,
+      None        => {
+        let msgs: Vec<String> = parser.errors.iter().map(|e| {
+          let (line, col) = parser.to_linecol(e.lo);
+          format!("{}:{}: {}", line + 1, col + 1, e.desc)
+        }).collect();
+        return Err(Error::Spec(msgs.join("; ")));
+      }
+    };
+
+    let mut spec = Spec::new();
+    for (key, value) in table.iter() {
+      match &key[..] {
+        "headers"   => {
+          for header in try!(strings(value, "headers")) {
+            spec.headers.push(String::from(header));
+          }
+        },
+        "class"     => {
+          for cls in try!(tables(value, "class")) {
+            spec.classes.push(try!(parse_class(cls)));
+          }
+        },
+        "namespace" => {
+          for ns in try!(tables(value, "namespace")) {
+            spec.namespaces.push(try!(parse_namespace(ns)));
+          }
+        },
+        _           => return Err(unknown_key("spec", key))
+      }
+    }
+
+    Ok(spec)
+  }
+
+  /// adds the headers and bindings of `other`, keeping the order of
+  /// the headers and dropping duplicates.
+  pub fn extend(&mut self, other: Spec) {
+    for header in other.headers.into_iter() {
+      if !self.headers.contains(&header) {
+        self.headers.push(header);
+      }
+    }
+    self.classes.extend(other.classes.into_iter());
+    self.namespaces.extend(other.namespaces.into_iter());
+  }
+
+  /// drops the classes and namespaces whose feature `enabled` rejects.
+  pub fn select_features<F: Fn(&str) -> bool>(&mut self, enabled: F) {
+    let selected = |feature: &Option<String>| match feature {
+      &Some(ref feature) => enabled(feature),
+      &None              => true
+    };
+    self.classes.retain(|cls| selected(&cls.feature));
+    self.namespaces.retain(|ns| selected(&ns.feature));
+  }
+
+  /// drops the classes and namespaces missing from `version` of
+  /// Crypto++, as given by `CRYPTOPP_VERSION`.
+  pub fn select_version(&mut self, version: u32) {
+    self.classes.retain(|cls| cls.since.map_or(true, |since| since <= version));
+    self.namespaces.retain(|ns| ns.since.map_or(true, |since| since <= version));
+  }
+
+  /// whether a class declares enums or constants, whose values only
+  /// the probe can give.
+  pub fn has_consts(&self) -> bool {
+    self.classes.iter().any(|cls| cls.class.has_consts())
+  }
+
+  /// the versions its classes and namespaces require, sorted.
+  pub fn versions(&self) -> Vec<u32> {
+    let mut versions: Vec<u32> = self.classes.iter().filter_map(|cls| cls.since)
+      .chain(self.namespaces.iter().filter_map(|ns| ns.since))
+      .collect();
+    versions.sort();
+    versions.dedup();
+
+    versions
+  }
+
+  /// the headers of the spec then the ones of its classes, without
+  /// duplicates.
+  pub fn all_headers(&self) -> Vec<&str> {
+    let mut headers: Vec<&str> = Vec::new();
+    let class_headers = self.classes.iter().flat_map(|cls| cls.headers.iter());
+    for header in self.headers.iter().chain(class_headers) {
+      if !headers.contains(&&header[..]) {
+        headers.push(header);
+      }
+    }
+
+    headers
+  }
+
+  /// writes a `#define` for each define of the classes, then an
+  /// `#include` for each header, see `all_headers`.
+  pub fn write_includes<T: Write>(&self, mut stream: T) -> Result<()> {
+    let mut defines: Vec<&str> = Vec::new();
+    for define in self.classes.iter().flat_map(|cls| cls.defines.iter()) {
+      if !defines.contains(&&define[..]) {
+        defines.push(define);
+      }
+    }
+    for define in defines.iter() {
+      let mut parts = define.splitn(2, '=');
+      try!(write!(stream, "#define {} {}\n",
+                  parts.next().unwrap(), parts.next().unwrap_or("1")));
+    }
+
+    for header in self.all_headers().iter() {
+      try!(write!(stream, "#include <{}>\n", header));
+    }
+
+    Ok(())
+  }
+
+  /// generates the bindings of every class and namespace, and writes
+  /// the Rust structs and traits the spec asks for into `out_path`.
+  pub fn generate<U: Write, V: Write, W: Write>(&self,
+                                                context: &mut Context<U, V, W>,
+                                                out_path: &Path) -> Result<()> {
+    for cls in self.classes.iter() {
+      for named in cls.named().iter() {
+        try!(named.generate_bindings(context));
+        if let Some(name) = cls.struct_name {
+          try!(named.generate_struct(out_path, name));
+        }
+        if let Some(name) = cls.trait_name {
+          try!(named.generate_trait(out_path, name));
+        }
+      }
+    }
+
+    for ns in self.namespaces.iter() {
+      try!(ns.namespace.generate_bindings(context));
+    }
+
+    Ok(())
+  }
+
+  /// writes the body of the probe printing the enums and constants of
+  /// every class, see `probe`.
+  pub fn generate_probe<T: Write>(&self, mut stream: T) -> Result<()> {
+    try!(self.write_includes(&mut stream));
+    try!(stream.write_all(b"\nusing namespace CryptoPP;\n\n"));
+    try!(probe::generate_probe_prelude(&mut stream));
+    for cls in self.classes.iter().filter(|cls| cls.class.has_consts()) {
+      for named in cls.named().iter() {
+        try!(named.generate_probe(&mut stream));
+      }
+    }
+    try!(probe::generate_probe_end(&mut stream));
+
+    Ok(())
+  }
+
+  /// writes the enums and constants of every class declaring some into
+  /// `out_path`, from the values printed by the probe.
+  pub fn generate_consts(&self, out_path: &Path, values: &probe::Values) -> Result<()> {
+    for cls in self.classes.iter().filter(|cls| cls.class.has_consts()) {
+      for named in cls.named().iter() {
+        try!(named.generate_consts(out_path, values));
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// parses a C++ type. `const byte*`, `byte const*` and `Integer&`
+/// become pointers and references; a class by value is `owned` and
+/// `enum Integer::Signedness` is `proto::cpp_enum`.
+pub fn parse_type(s: &str) -> Result<BasicType> {
+  let s = leak_str(s.trim());
+
+  if s.starts_with("enum ") {
+    let name = s[5..].trim();
+    if name.len() < 1 || name.contains('*') || name.contains('&') {
+      return Err(Error::Spec(format!("unsupported type `{}`", s)));
+    }
+    return Ok(proto::cpp_enum(name.as_bytes()));
+  }
+
+  let (body, indirection) = if s.ends_with('*') {
+    (&s[..s.len() - 1], Some('*'))
+  } else if s.ends_with('&') {
+    (&s[..s.len() - 1], Some('&'))
+  } else {
+    (s, None)
+  };
+
+  let mut body = body.trim();
+  let mut is_const = false;
+  if body.starts_with("const ") {
+    body = body[6..].trim();
+    is_const = true;
+  } else if body.ends_with(" const") {
+    body = body[..body.len() - 6].trim();
+    is_const = true;
+  }
+
+  if body.len() < 1 || body.contains('*') || body.contains('&') {
+    return Err(Error::Spec(format!("unsupported type `{}`", s)));
+  }
+
+  let ctype = match primitive(body) {
+    Some(ctype)                    => ctype,
+    None if is_class_name(body)    => CType::Custom(body.as_bytes()),
+    None                           => return Err(Error::Spec(format!("unsupported type `{}`", s)))
+  };
+  Ok(match (indirection, is_const) {
+    (Some('*'), true)  => BasicType::ConstPointer(ctype),
+    (Some('*'), false) => BasicType::MutPointer(ctype),
+    (Some(_), true)    => BasicType::ConstRef(ctype),
+    (Some(_), false)   => BasicType::MutRef(ctype),
+    (None, _)          => match ctype {
+      CType::Custom(_) => BasicType::Owned(ctype),
+      _                => BasicType::Simple(ctype)
+    }
+  })
+}
+
+/// parses a version such as `5.6.4` into 564, as Crypto++ spells it
+/// in `CRYPTOPP_VERSION`. the minor and patch numbers are single
+/// digits.
+pub fn parse_version(s: &str) -> Result<u32> {
+  let bad_version = || Error::Spec(format!("bad version `{}`", s));
+  let mut version = 0;
+  let mut parts = 0;
+
+  for (i, part) in s.trim().split('.').enumerate() {
+    let n: u32 = try!(part.parse().map_err(|_| bad_version()));
+    if i > 2 || (i > 0 && n > 9) {
+      return Err(bad_version());
+    }
+    version += n * [100, 10, 1][i];
+    parts += 1;
+  }
+
+  if parts < 2 { Err(bad_version()) } else { Ok(version) }
+}
+
+/// parses a class name such as `CryptoPP::CBC_Mode<AES>::Encryption`.
+/// the parts before the first templated one are its namespace, the
+/// parts after it are nested classes.
+pub fn parse_class_path<'a>(s: &'a str) -> Result<ClassPath<'a>> {
+  let s = s.trim();
+  let bad_path = || Error::Spec(format!("bad class name `{}`", s));
+
+  let mut parts = Vec::new();
+  for part in split_top_level(s, "::") {
+    let part = part.trim();
+    let (name, args) = match part.find('<') {
+      Some(i) if part.ends_with('>') => (part[..i].trim(), Some(&part[i + 1..part.len() - 1])),
+      Some(_)                        => return Err(bad_path()),
+      None                           => (part, None)
+    };
+    if name.len() < 1 {
+      return Err(bad_path());
+    }
+    parts.push((name, args));
+  }
+
+  let templated = parts.iter().position(|&(_, args)| args.is_some());
+  let name_idx = templated.unwrap_or(parts.len() - 1);
+  if parts.iter().skip(name_idx + 1).any(|&(_, args)| args.is_some()) {
+    return Err(bad_path());
+  }
+
+  let namespace = parts[..name_idx].iter().map(|&(n, _)| n.as_bytes()).collect();
+  let mut path = class_path(namespace, parts[name_idx].0.as_bytes());
+  if let Some(args) = parts[name_idx].1 {
+    for arg in split_top_level(args, ",") {
+      path = path.template_arg(try!(parse_class_path(arg)));
+    }
+  }
+  for &(nested, _) in parts[name_idx + 1..].iter() {
+    path = path.nested(nested.as_bytes());
+  }
+
+  Ok(path)
+}
+
+/// splits `s` at each `sep` outside of template brackets.
+fn split_top_level<'a>(s: &'a str, sep: &str) -> Vec<&'a str> {
+  let mut parts = Vec::new();
+  let mut depth = 0;
+  let mut start = 0;
+  let mut i = 0;
+  while i < s.len() {
+    match s.as_bytes()[i] {
+      b'<' => depth += 1,
+      b'>' => depth -= 1,
+      _ if depth == 0 && s[i..].starts_with(sep) => {
+        parts.push(&s[start..i]);
+        i += sep.len();
+        start = i;
+        continue;
+      },
+      _    => ()
+    }
+    i += 1;
+  }
+  parts.push(&s[start..]);
+
+  parts
+}
+
+/// C++ keywords that can be part of a type but not of a class name.
+const TYPE_KEYWORDS: &'static [&'static str] = &[
+  "void", "bool", "char", "wchar_t", "short", "int", "long", "signed", "unsigned", "float",
+  "double", "const", "volatile", "enum", "struct", "class", "typename",
+];
+
+/// whether `s` can be a class name rather than a misspelled or
+/// unsupported primitive such as `sizet` or `unsigned long`: a class
+/// path of identifiers that are not keywords, naming a capitalized
+/// class as Crypto++ does.
+fn is_class_name(s: &str) -> bool {
+  let is_ident = |word: &str| {
+    let mut chars = word.chars();
+    chars.next().map_or(false, |c| c.is_alphabetic() || c == '_') &&
+      chars.all(|c| c.is_alphanumeric() || c == '_') &&
+      !TYPE_KEYWORDS.contains(&word)
+  };
+  let words_ok = s.split(|c| c == '<' || c == '>' || c == ',' || c == ':')
+    .map(|word| word.trim())
+    .all(|word| word.len() < 1 || is_ident(word));
+  let class = split_top_level(s, "::").pop().unwrap_or("").trim();
+
+  words_ok && !s.replace("::", "").contains(':') && parse_class_path(s).is_ok() &&
+    class.chars().next().map_or(false, |c| c.is_uppercase())
+}
+
+/// the type spelled `name`, if it is not a class.
+pub fn primitive(name: &str) -> Option<CType> {
+  Some(match name {
+    "void"                                        => CType::Void,
+    "bool"                                        => CType::Bool,
+    "char"                                        => CType::Char,
+    "unsigned char"                               => CType::UChar,
+    "byte"                                        => CType::Byte,
+    "int"                                         => CType::Int,
+    "unsigned" | "unsigned int"                   => CType::UInt,
+    "long" | "signed long"                        => CType::Long,
+    "long long" | "signed long long"              => CType::LongLong,
+    "size_t"                                      => CType::SizeT,
+    "word"                                        => CType::Word,
+    "word32"                                      => CType::Word32,
+    "word64"                                      => CType::Word64,
+    "lword"                                       => CType::LWord,
+    "double"                                      => CType::Double,
+    "std::string" | "string"                      => CType::StdString,
+    "utf8 std::string" | "utf8 string"            => CType::Utf8String,
+    _                                             => return None
+  })
+}
+
+/// the generator keeps names as `&'static [u8]`, a build script only
+/// parses its specs once so they are leaked.
+pub fn leak_str(s: &str) -> &'static str {
+  Box::leak(String::from(s).into_boxed_str())
+}
+
+fn unknown_key(what: &str, key: &str) -> Error {
+  Error::Spec(format!("unknown key `{}` in {}", key, what))
+}
+
+fn string<'t>(value: &'t toml::Value, what: &str) -> Result<&'t str> {
+  value.as_str().ok_or_else(|| Error::Spec(format!("`{}` must be a string", what)))
+}
+
+fn strings<'t>(value: &'t toml::Value, what: &str) -> Result<Vec<&'t str>> {
+  let values = try!(value.as_slice().ok_or_else(|| {
+    Error::Spec(format!("`{}` must be an array of strings", what))
+  }));
+
+  values.iter().map(|v| string(v, what)).collect()
+}
+
+fn tables<'t>(value: &'t toml::Value, what: &str) -> Result<Vec<&'t toml::Table>> {
+  let values = try!(value.as_slice().ok_or_else(|| {
+    Error::Spec(format!("`{}` must be an array of tables", what))
+  }));
+
+  values.iter().map(|v| {
+    v.as_table().ok_or_else(|| Error::Spec(format!("`{}` must be an array of tables", what)))
+  }).collect()
+}
+
+fn boolean(value: &toml::Value, what: &str) -> Result<bool> {
+  value.as_bool().ok_or_else(|| Error::Spec(format!("`{}` must be a boolean", what)))
+}
+
+fn parse_args(value: &toml::Value) -> Result<FunctionArgs> {
+  let mut args = Vec::new();
+  for arg in try!(strings(value, "args")) {
+    args.push(try!(parse_type(arg)));
+  }
+
+  Ok(FunctionArgs::new(args))
+}
+
+/// a method, static method or function entry: its alias, C++ name,
+/// constness and prototype.
+fn parse_function(table: &toml::Table, what: &str)
+    -> Result<(&'static [u8], &'static [u8], bool, Function)> {
+  let mut name = None;
+  let mut alias = None;
+  let mut is_const = false;
+  let mut ret = proto::void();
+  let mut args = FunctionArgs::new(Vec::new());
+
+  for (key, value) in table.iter() {
+    match &key[..] {
+      "name"    => name = Some(leak_str(try!(string(value, "name"))).as_bytes()),
+      "alias"   => alias = Some(leak_str(try!(string(value, "alias"))).as_bytes()),
+      "const"   => is_const = try!(boolean(value, "const")),
+      "returns" => ret = try!(parse_type(try!(string(value, "returns")))),
+      "args"    => args = try!(parse_args(value)),
+      _         => return Err(unknown_key(what, key))
+    }
+  }
+
+  let name = try!(name.ok_or_else(|| Error::Spec(format!("{} without a `name`", what))));
+  Ok((alias.unwrap_or(name), name, is_const, Function { ret: ret, args: args }))
+}
+
+fn parse_functions<F>(value: &toml::Value, what: &str, mut add: F) -> Result<()>
+    where F: FnMut(&'static [u8], &'static [u8], bool, Function) {
+  for table in try!(tables(value, what)) {
+    let (alias, name, is_const, function) = try!(parse_function(table, what));
+    add(alias, name, is_const, function);
+  }
+
+  Ok(())
+}
+
+fn parse_class(table: &toml::Table) -> Result<ClassSpec> {
+  let mut cls = class();
+  let mut paths = Vec::new();
+  let mut struct_name = None;
+  let mut trait_name = None;
+  let mut feature = None;
+  let mut headers = Vec::new();
+  let mut defines = Vec::new();
+  let mut since = None;
+
+  for (key, value) in table.iter() {
+    match &key[..] {
+      "name"                => paths.push(try!(parse_class_path(leak_str(try!(string(value, "name")))))),
+      "names"               => {
+        for name in try!(strings(value, "names")) {
+          paths.push(try!(parse_class_path(leak_str(name))));
+        }
+      },
+      "struct"              => struct_name = Some(leak_str(try!(string(value, "struct"))).as_bytes()),
+      "trait"               => trait_name = Some(leak_str(try!(string(value, "trait"))).as_bytes()),
+      "feature"             => feature = Some(String::from(try!(string(value, "feature")))),
+      "defines"             => {
+        for define in try!(strings(value, "defines")) {
+          defines.push(String::from(define));
+        }
+      },
+      "since"               => since = Some(try!(parse_version(try!(string(value, "since"))))),
+      "headers"             => {
+        for header in try!(strings(value, "headers")) {
+          headers.push(String::from(header));
+        }
+      },
+      "bases"               => {
+        for base in try!(strings(value, "bases")) {
+          cls.add_base(try!(parse_class_path(leak_str(base))));
+        }
+      },
+      "constructors"        => {
+        for ctor in try!(tables(value, "constructors")) {
+          let mut name: &'static [u8] = b"";
+          let mut args = FunctionArgs::new(Vec::new());
+          for (key, value) in ctor.iter() {
+            match &key[..] {
+              "name" => name = leak_str(try!(string(value, "name"))).as_bytes(),
+              "args" => args = try!(parse_args(value)),
+              _      => return Err(unknown_key("constructor", key))
+            }
+          }
+          cls.add_constructor(name, args);
+        }
+      },
+      "methods"             => try!(parse_functions(value, "method", |alias, name, is_const, f| {
+        cls.add_method_as(alias, name, is_const, f)
+      })),
+      "static_methods"      => try!(parse_functions(value, "static method", |alias, name, _, f| {
+        cls.add_static_method_as(alias, name, f)
+      })),
+      "functions"           => try!(parse_functions(value, "function", |alias, name, _, f| {
+        cls.add_function_as(alias, name, f)
+      })),
+      "overridable_methods" => try!(parse_functions(value, "overridable method", |alias, name, is_const, f| {
+        cls.add_overridable_method_as(alias, name, is_const, f)
+      })),
+      "operators"           => {
+        for op in try!(tables(value, "operators")) {
+          try!(parse_operator(&mut cls, op));
+        }
+      },
+      "enums"               => {
+        for e in try!(tables(value, "enums")) {
+          try!(parse_enum(&mut cls, e));
+        }
+      },
+      "constants"           => {
+        for constant in try!(tables(value, "constants")) {
+          try!(parse_constant(&mut cls, constant));
+        }
+      },
+      _                     => return Err(unknown_key("class", key))
+    }
+  }
+
+  if paths.len() < 1 {
+    return Err(Error::Spec(String::from("class without a `name`")));
+  }
+
+  Ok(ClassSpec {
+    paths:       paths,
+    class:       cls,
+    struct_name: struct_name,
+    trait_name:  trait_name,
+    feature:     feature,
+    headers:     headers,
+    defines:     defines,
+    since:       since,
+  })
+}
+
+fn parse_operator(cls: &mut Class, table: &toml::Table) -> Result<()> {
+  let mut token = None;
+  let mut alias = None;
+  let mut with_trait = false;
+  let mut ret = proto::void();
+  let mut args = FunctionArgs::new(Vec::new());
+
+  for (key, value) in table.iter() {
+    match &key[..] {
+      "op"      => token = Some(try!(string(value, "op"))),
+      "alias"   => alias = Some(leak_str(try!(string(value, "alias"))).as_bytes()),
+      "impl"    => with_trait = try!(boolean(value, "impl")),
+      "returns" => ret = try!(parse_type(try!(string(value, "returns")))),
+      "args"    => args = try!(parse_args(value)),
+      _         => return Err(unknown_key("operator", key))
+    }
+  }
+
+  let token = try!(token.ok_or_else(|| Error::Spec(String::from("operator without an `op`"))));
+  let op = try!(Operator::from_cpp(token, args.len() > 0).ok_or_else(|| {
+    Error::Spec(format!("unsupported operator `{}`", token))
+  }));
+
+  let function = Function { ret: ret, args: args };
+  cls.add_operator_as(alias.unwrap_or(op.name()), op, with_trait, function);
+
+  Ok(())
+}
+
+fn parse_enum(cls: &mut Class, table: &toml::Table) -> Result<()> {
+  let mut name = None;
+  let mut variants = Vec::new();
+
+  for (key, value) in table.iter() {
+    match &key[..] {
+      "name"     => name = Some(leak_str(try!(string(value, "name"))).as_bytes()),
+      "variants" => {
+        for variant in try!(strings(value, "variants")) {
+          variants.push(leak_str(variant).as_bytes());
+        }
+      },
+      _          => return Err(unknown_key("enum", key))
+    }
+  }
+
+  let name = try!(name.ok_or_else(|| Error::Spec(String::from("enum without a `name`"))));
+  if variants.len() < 1 {
+    return Err(Error::Spec(String::from("enum without `variants`")));
+  }
+  cls.add_enum(name, variants);
+
+  Ok(())
+}
+
+fn parse_constant(cls: &mut Class, table: &toml::Table) -> Result<()> {
+  let mut name = None;
+  let mut ctype = None;
+
+  for (key, value) in table.iter() {
+    match &key[..] {
+      "name" => name = Some(leak_str(try!(string(value, "name"))).as_bytes()),
+      "type" => ctype = Some(try!(parse_type(try!(string(value, "type"))))),
+      _      => return Err(unknown_key("constant", key))
+    }
+  }
+
+  let name = try!(name.ok_or_else(|| Error::Spec(String::from("constant without a `name`"))));
+  let ctype = try!(ctype.ok_or_else(|| Error::Spec(String::from("constant without a `type`"))));
+  cls.add_constant(name, ctype);
+
+  Ok(())
+}
+
+fn parse_namespace(table: &toml::Table) -> Result<NamespaceSpec> {
+  let mut cls = class();
+  let mut namespace = Vec::new();
+  let mut feature = None;
+  let mut since = None;
+
+  for (key, value) in table.iter() {
+    match &key[..] {
+      "name"      => {
+        let name = leak_str(try!(string(value, "name")));
+        namespace = name.split("::").map(|part| part.trim().as_bytes()).collect();
+      },
+      "functions" => try!(parse_functions(value, "function", |alias, name, _, f| {
+        cls.add_function_as(alias, name, f)
+      })),
+      "feature"   => feature = Some(String::from(try!(string(value, "feature")))),
+      "since"     => since = Some(try!(parse_version(try!(string(value, "since"))))),
+      _           => return Err(unknown_key("namespace", key))
+    }
+  }
+
+  Ok(NamespaceSpec {
+    namespace: Namespace::new(namespace, cls),
+    feature:   feature,
+    since:     since,
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use proto::{BasicType, CType};
+  use super::{parse_type, parse_class_path, parse_version, Spec};
+
+  fn cpp_type(s: &str) -> String {
+    let mut out = Vec::new();
+    parse_type(s).unwrap().generate_cpp(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+  }
+
+  fn cpp_path(s: &str) -> String {
+    let mut out = Vec::new();
+    parse_class_path(s).unwrap().generate_cpp_path(&mut out).unwrap();
+    String::from_utf8(out).unwrap()
+  }
+
+  #[test]
+  fn types() {
+    assert_eq!(cpp_type("const byte*"), "byte const*");
+    assert_eq!(cpp_type("byte const *"), "byte const*");
+    assert_eq!(cpp_type("unsigned int"), "unsigned int");
+    assert_eq!(cpp_type("Integer&"), "Integer*");
+    assert!(parse_type("Integer").unwrap().is_owned());
+    assert!(parse_type("const Integer&").unwrap().is_ref());
+    assert!(parse_type("void").unwrap().is_void());
+
+    match parse_type("signed long long").unwrap() {
+      BasicType::Simple(CType::LongLong) => (),
+      _                                  => panic!("not a long long")
+    }
+
+    assert!(parse_type("byte**").is_err());
+
+    assert_eq!(cpp_type("enum Integer::Signedness"), "Integer::Signedness");
+    assert_eq!(cpp_type("const std::string&"), "rcpp_string");
+    assert!(parse_type("utf8 std::string").unwrap().is_string());
+    assert!(!parse_type("std::string*").unwrap().is_string());
+    assert!(parse_type("enum Integer::Signedness*").is_err());
+
+    assert_eq!(cpp_type("const Weak::MD5&"), "Weak::MD5 const*");
+    assert!(parse_type("CBC_Mode<AES>::Encryption").unwrap().is_owned());
+    for bad in ["unsigned long", "short", "float", "sizet", "Foo Bar", "A:B", "A<>"].iter() {
+      assert!(parse_type(bad).is_err(), "`{}` parsed", bad);
+    }
+  }
+
+  #[test]
+  fn class_paths() {
+    assert_eq!(cpp_path("CryptoPP::Integer"), "CryptoPP::Integer");
+    assert_eq!(cpp_path("CBC_Mode<AES>::Encryption"), "CBC_Mode<AES>::Encryption");
+    assert_eq!(cpp_path("RSAES<OAEP<SHA256> >::Encryptor"), "RSAES<OAEP<SHA256> >::Encryptor");
+    assert_eq!(cpp_path("Pair< A, B<C> >"), "Pair<A, B<C> >");
+
+    let mut c_path = Vec::new();
+    parse_class_path("CryptoPP::Weak::MD5").unwrap().generate_c_path(&mut c_path).unwrap();
+    assert_eq!(c_path, b"CryptoPP_SWeak_SMD5");
+
+    assert!(parse_class_path("A<B>::C<D>").is_err());
+    assert!(parse_class_path("A<B").is_err());
+  }
+
+  #[test]
+  fn errors() {
+    assert!(Spec::parse("[[class]]\nname = \"A\"\nmethod = []\n").is_err());
+    assert!(Spec::parse("[[class]]\nconstructors = [{}]\n").is_err());
+    assert!(Spec::parse("[[class]]\nname = \"A\"\nmethods = [{ returns = \"int\" }]\n").is_err());
+    assert!(Spec::parse("headers = [").is_err());
+    assert!(Spec::parse("[[class]]\nname = \"A\"\noperators = [{ op = \"^\", args = [\"int\"] }]\n").is_err());
+    assert!(Spec::parse("[[class]]\nname = \"A\"\nenums = [{ name = \"E\" }]\n").is_err());
+    assert!(Spec::parse("[[class]]\nname = \"A\"\nconstants = [{ name = \"N\" }]\n").is_err());
+    assert!(Spec::parse("[[class]]\nname = \"A\"\nfeature = [\"a\"]\n").is_err());
+  }
+
+  #[test]
+  fn features() {
+    let mut spec = Spec::parse(r#"
+      headers = ["cryptopp/cryptlib.h"]
+
+      [[class]]
+      name = "HashTransformation"
+
+      [[class]]
+      names = ["SHA1"]
+      feature = "sha1"
+      headers = ["cryptopp/sha.h", "cryptopp/cryptlib.h"]
+
+      [[class]]
+      names = ["SHA3_256"]
+      feature = "sha3"
+      headers = ["cryptopp/sha3.h"]
+
+      [[class]]
+      names = ["Weak::MD5"]
+      feature = "weak"
+      headers = ["cryptopp/md5.h"]
+      defines = ["CRYPTOPP_ENABLE_NAMESPACE_WEAK=1"]
+    "#).unwrap();
+    assert_eq!(spec.all_headers(), vec!["cryptopp/cryptlib.h", "cryptopp/sha.h",
+                                        "cryptopp/sha3.h", "cryptopp/md5.h"]);
+
+    let mut includes = Vec::new();
+    spec.write_includes(&mut includes).unwrap();
+    assert!(includes.starts_with(b"#define CRYPTOPP_ENABLE_NAMESPACE_WEAK 1\n#include"));
+
+    spec.select_features(|feature| feature == "sha3");
+    assert_eq!(spec.classes.len(), 2);
+    assert_eq!(spec.all_headers(), vec!["cryptopp/cryptlib.h", "cryptopp/sha3.h"]);
+  }
+
+  #[test]
+  fn versions() {
+    assert_eq!(parse_version("5.6.4").unwrap(), 564);
+    assert_eq!(parse_version("8.0").unwrap(), 800);
+    assert!(parse_version("8").is_err());
+    assert!(parse_version("5.10").is_err());
+    assert!(parse_version("5.6.4.1").is_err());
+
+    let mut spec = Spec::parse(r#"
+      [[class]]
+      names = ["SHA3_256"]
+      since = "5.6.4"
+
+      [[class]]
+      names = ["SHA1"]
+
+      [[namespace]]
+      name = "CryptoPP"
+      since = "6.0"
+      functions = [{ name = "LibraryVersion", returns = "int" }]
+    "#).unwrap();
+    assert_eq!(spec.versions(), vec![564, 600]);
+    spec.select_version(563);
+    assert_eq!(spec.classes.len(), 1);
+    assert_eq!(spec.namespaces.len(), 0);
+  }
+}
@@ -0,0 +1,752 @@
+//! runs class descriptions through the generator and compares the
+//! output against the files in `tests/golden`. set `GEN_BLESS=1` to
+//! rewrite the expected files from the current output.
+
+#[macro_use]
+extern crate rust_cryptopp_gen as gen;
+
+use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+
+use gen::proto::*;
+
+fn golden_path(fname: &str) -> PathBuf {
+  PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden").join(fname)
+}
+
+fn check_golden(fname: &str, actual: &[u8]) {
+  let path = golden_path(fname);
+
+  if env::var("GEN_BLESS").is_ok() {
+    File::create(&path).unwrap().write_all(actual).unwrap();
+    return;
+  }
+
+  let mut expected = Vec::new();
+  File::open(&path).unwrap().read_to_end(&mut expected).unwrap();
+
+  assert!(expected == actual,
+          "generated output does not match {}:\n{}",
+          path.display(),
+          String::from_utf8_lossy(actual));
+}
+
+fn check_bindings<T: std::borrow::Borrow<gen::Class>>(name: &str,
+                                                      cls: &gen::NamedClass<T>) {
+  let mut ctx = gen::Context::new(Vec::new(), Vec::new());
+  cls.generate_bindings(&mut ctx).unwrap();
+
+  check_golden(&format!("{}.cpp", name), &ctx.cpp_stream);
+  check_golden(&format!("{}.rs", name), &ctx.rs_binding_stream);
+}
+
+#[test]
+fn hash_transformation() {
+  let ht = class!(b"HashTransformation" => {
+    constant methods {
+      uint(), b"DigestSize";
+    }
+    mutable methods {
+      void(), b"Update",     const_ptr(UChar), size_t();
+      void(), b"Final",      mut_ptr(UChar);
+      void(), b"Restart";
+    }
+  });
+
+  check_bindings("hash_transformation", &ht);
+}
+
+#[test]
+fn prototype_hash() {
+  let hash = prototype_class!({
+    constructors {
+      b"";
+    }
+  });
+  let sha3 = class!(vec![], b"SHA3_256", &hash);
+
+  check_bindings("sha3_256", &sha3);
+
+  let mut stream = Vec::new();
+  sha3.write_struct(b"Hash", &mut stream).unwrap();
+  check_golden("sha3_256_struct.rs", &stream);
+}
+
+#[test]
+fn namespaced_ctors() {
+  let integer = class!(vec![b"CryptoPP"], b"Integer" => {
+    constructors {
+      b"";
+      b"copy",      const_ref(Custom(b"Integer"));
+      b"from_long", long();
+    }
+  });
+
+  check_bindings("integer", &integer);
+}
+
+#[test]
+fn many_args() {
+  let ski = class!(b"SimpleKeyingInterface" => {
+    mutable methods {
+      void(), b"SetKeyWithIV", const_ptr(UChar), size_t(), const_ptr(UChar), size_t();
+    }
+  });
+
+  check_bindings("many_args_keying", &ski);
+
+  let hash = class!(b"HashTransformation" => {
+    mutable methods {
+      void(), b"CalculateTruncatedDigest",
+        mut_ptr(UChar), size_t(), const_ptr(UChar), size_t();
+    }
+  });
+
+  check_bindings("many_args_hash", &hash);
+}
+
+#[test]
+fn primitive_types() {
+  let integer = class!(b"Integer" => {
+    constant methods {
+      bool(),  b"IsNegative";
+      byte(),  b"GetByte",    size_t();
+      lword(), b"GetBits",    size_t(), size_t();
+      long(),  b"ConvertToLong";
+    }
+    mutable methods {
+      void(),  b"SetByte",    size_t(), byte();
+      void(),  b"Randomize",  mut_ref(Custom(b"RandomNumberGenerator")), size_t();
+    }
+  });
+
+  check_bindings("primitive_types_integer", &integer);
+
+  let rng = class!(b"RandomNumberGenerator" => {
+    mutable methods {
+      word32(), b"GenerateWord32", word32(), word32();
+    }
+  });
+
+  check_bindings("primitive_types_rng", &rng);
+
+  let ma = class!(b"ModularArithmetic" => {
+    constant methods {
+      const_ref(Custom(b"Integer")), b"GetModulus";
+    }
+  });
+
+  check_bindings("primitive_types_ref_return", &ma);
+
+  let timer = class!(b"Timer" => {
+    mutable methods {
+      double(), b"ElapsedTimeAsDouble";
+    }
+  });
+
+  check_bindings("primitive_types_timer", &timer);
+
+  let exception = class!(b"Exception" => {
+    constant methods {
+      c_str(), b"what";
+    }
+  });
+
+  check_bindings("primitive_types_c_str", &exception);
+}
+
+#[test]
+fn templates() {
+  let hmac = prototype_class!({
+    constructors {
+      b"with_key", const_ptr(UChar), size_t();
+    }
+  });
+
+  let mut cpp = Vec::new();
+  let mut rs = Vec::new();
+  for hash in [&b"SHA1"[..], &b"SHA3_256"[..]].iter() {
+    let path = gen::class_path(vec![], b"HMAC")
+                 .template_arg(gen::class_path(vec![], hash));
+    let cls = class!(path path, &hmac);
+
+    let mut ctx = gen::Context::new(&mut cpp, &mut rs);
+    cls.generate_bindings(&mut ctx).unwrap();
+  }
+  check_golden("templates_hmac.cpp", &cpp);
+  check_golden("templates_hmac.rs", &rs);
+
+  let cbc = class!(path gen::class_path(vec![b"CryptoPP"], b"CBC_Mode")
+                     .template_arg(gen::class_path(vec![], b"AES"))
+                     .nested(b"Encryption") => {
+    constructors {
+      b"";
+      b"with_key_iv", const_ptr(UChar), size_t(), const_ptr(UChar);
+    }
+  });
+  assert_eq!(cbc.c_path().unwrap(), "CryptoPP_SCBC__Mode_LAES_R_SEncryption");
+  check_bindings("templates_cbc", &cbc);
+
+  let mut stream = Vec::new();
+  cbc.write_struct(b"Encryption", &mut stream).unwrap();
+  check_golden("templates_cbc_struct.rs", &stream);
+
+  let signer = class!(path gen::class_path(vec![], b"RSASS")
+                        .template_arg(gen::class_path(vec![], b"PSS"))
+                        .template_arg(gen::class_path(vec![], b"SHA256"))
+                        .nested(b"Signer") => {
+    constructors {
+      b"";
+    }
+  });
+  assert_eq!(signer.c_path().unwrap(), "RSASS_LPSS_CSHA256_R_SSigner");
+
+  let oaep = gen::class_path(vec![], b"OAEP")
+               .template_arg(gen::class_path(vec![], b"SHA256"));
+  let nested = class!(path gen::class_path(vec![], b"RSAES")
+                        .template_arg(oaep)
+                        .nested(b"Encryptor") => {
+    constructors {
+      b"";
+    }
+  });
+  assert_eq!(nested.c_path().unwrap(), "RSAES_LOAEP_LSHA256_R_R_SEncryptor");
+  check_bindings("templates_nested_arg", &nested);
+}
+
+#[test]
+fn distinct_c_paths() {
+  let paths = vec![
+    gen::class_path(vec![], b"A_SB"),
+    gen::class_path(vec![], b"A").nested(b"B"),
+    gen::class_path(vec![b"A"], b"SB"),
+    gen::class_path(vec![], b"A_B"),
+    gen::class_path(vec![b"A_"], b"B"),
+    gen::class_path(vec![b"A"], b"_B"),
+    gen::class_path(vec![], b"A_LB_R"),
+    gen::class_path(vec![], b"A").template_arg(gen::class_path(vec![], b"B")),
+    gen::class_path(vec![], b"A").template_arg(gen::class_path(vec![], b"B_CC")),
+    gen::class_path(vec![], b"A").template_arg(gen::class_path(vec![], b"B"))
+                                 .template_arg(gen::class_path(vec![], b"C")),
+  ];
+
+  let mut stream = Vec::new();
+  let mut c_paths = Vec::new();
+  for path in paths.iter() {
+    let mut c_path = Vec::new();
+    path.generate_c_path(&mut c_path).unwrap();
+    assert!(!c_paths.contains(&c_path), "two paths share {}", String::from_utf8_lossy(&c_path));
+
+    path.generate_cpp_path(&mut stream).unwrap();
+    stream.write_all(b" ").unwrap();
+    stream.write_all(&c_path).unwrap();
+    stream.write_all(b"\n").unwrap();
+    c_paths.push(c_path);
+  }
+  check_golden("c_paths.txt", &stream);
+}
+
+#[test]
+fn statics_and_functions() {
+  let sha256 = class!(vec![b"CryptoPP"], b"SHA256" => {
+    static methods {
+      c_str(), b"StaticAlgorithmName";
+      void(),  b"InitState", mut_ptr(Word32);
+    }
+    functions {
+      bool(),  b"VerifyBufsEqual", const_ptr(Byte), const_ptr(Byte), size_t();
+    }
+  });
+
+  check_bindings("static_methods", &sha256);
+
+  let cryptopp = namespace!(vec![b"CryptoPP"] => {
+    functions {
+      void(), b"OS_GenerateRandomBlock", bool(), mut_ptr(Byte), size_t();
+      bool(), b"VerifyBufsEqual",        const_ptr(Byte), const_ptr(Byte), size_t();
+      int(),  b"HeaderVersion";
+    }
+  });
+
+  let mut ctx = gen::Context::new(Vec::new(), Vec::new());
+  cryptopp.generate_bindings(&mut ctx).unwrap();
+  check_golden("namespace_functions.cpp", &ctx.cpp_stream);
+  check_golden("namespace_functions.rs", &ctx.rs_binding_stream);
+}
+
+#[test]
+fn overload_aliases() {
+  let integer = class!(b"Integer" => {
+    constant methods {
+      void(),   b"Encode",                   mut_ptr(Byte), size_t();
+      void(),   b"Encode" => b"EncodeToSink",
+        mut_ref(Custom(b"BufferedTransformation")), size_t();
+      size_t(), b"MinEncodedSize";
+    }
+    mutable methods {
+      void(),   b"Decode" => b"DecodeBytes", const_ptr(Byte), size_t();
+    }
+  });
+
+  check_bindings("overload_aliases", &integer);
+
+  let sha1 = class!(b"SHA1" => {
+    static methods {
+      c_str(), b"StaticAlgorithmName" => b"Name";
+    }
+    functions {
+      bool(), b"VerifyBufsEqual" => b"ConstantTimeEq",
+        const_ptr(Byte), const_ptr(Byte), size_t();
+    }
+  });
+
+  check_bindings("overload_aliases_static", &sha1);
+}
+
+#[test]
+fn owned_returns() {
+  let integer = class!(b"Integer" => {
+    constructors {
+      b"";
+    }
+    constant methods {
+      owned(Custom(b"Integer")), b"Squared";
+      owned(Custom(b"Integer")), b"Plus", const_ref(Custom(b"Integer"));
+    }
+    static methods {
+      owned(Custom(b"Integer")), b"Power2", size_t();
+      owned(Custom(b"Integer")), b"Gcd",
+        owned(Custom(b"Integer")), owned(Custom(b"Integer"));
+    }
+    functions {
+      owned(Custom(b"Integer")), b"a_exp_b_mod_c",
+        const_ref(Custom(b"Integer")),
+        const_ref(Custom(b"Integer")),
+        const_ref(Custom(b"Integer"));
+    }
+  });
+
+  check_bindings("owned_returns_integer", &integer);
+
+  let algorithm = class!(b"Algorithm" => {
+    constant methods {
+      owned(Custom(b"std::string")), b"AlgorithmName";
+    }
+  });
+
+  check_bindings("owned_returns_string", &algorithm);
+}
+
+#[test]
+fn base_classes() {
+  let hash = prototype_class!({
+    bases {
+      gen::class_path(vec![], b"HashTransformation");
+      gen::class_path(vec![], b"Algorithm");
+    }
+    constructors {
+      b"";
+    }
+  });
+  let sha3 = class!(vec![], b"SHA3_256", &hash);
+
+  check_bindings("base_classes", &sha3);
+
+  let mut stream = Vec::new();
+  sha3.write_struct(b"Hash", &mut stream).unwrap();
+  check_golden("base_classes_struct.rs", &stream);
+
+  let hmac = class!(path gen::class_path(vec![], b"HMAC")
+                      .template_arg(gen::class_path(vec![], b"SHA256")) => {
+    bases {
+      gen::class_path(vec![], b"MessageAuthenticationCode");
+      gen::class_path(vec![], b"SimpleKeyingInterface");
+    }
+  });
+
+  check_bindings("base_classes_templated", &hmac);
+}
+
+#[test]
+fn safe_wrappers() {
+  let integer = class!(b"Integer" => {
+    constructors {
+      b"";
+      b"copy",       const_ref(Custom(b"Integer"));
+      b"from_long",  long();
+      b"from_bytes", const_ptr(Byte), size_t();
+    }
+    constant methods {
+      bool(),   b"IsNegative";
+      uint(),   b"BitCount";
+      size_t(), b"MinEncodedSize";
+      void(),   b"Encode", mut_ptr(Byte), size_t();
+      owned(Custom(b"Integer")), b"Squared";
+      owned(Custom(b"Integer")), b"Plus", const_ref(Custom(b"Integer"));
+      const_ptr(Char), b"Name";
+    }
+    mutable methods {
+      void(), b"Decode", const_ptr(Byte), size_t();
+      void(), b"swap", mut_ref(Custom(b"Integer"));
+      void(), b"Randomize", mut_ref(Custom(b"RandomNumberGenerator")), size_t();
+    }
+    static methods {
+      owned(Custom(b"Integer")), b"Power2", size_t();
+    }
+  });
+
+  let mut stream = Vec::new();
+  integer.write_struct(b"Integer", &mut stream).unwrap();
+  check_golden("safe_wrappers_integer.rs", &stream);
+
+  let ht = class!(b"HashTransformation" => {
+    mutable methods {
+      void(), b"Final", mut_ptr(Byte);
+      void(), b"TruncatedFinal" => b"Final_truncated", mut_ptr(Byte), size_t();
+    }
+  });
+
+  let mut stream = Vec::new();
+  ht.write_struct(b"Transformation", &mut stream).unwrap();
+  check_golden("safe_wrappers_hash.rs", &stream);
+}
+
+#[test]
+fn overridable_methods() {
+  let rng = class!(b"RandomNumberGenerator" => {
+    bases {
+      gen::class_path(vec![], b"Algorithm");
+    }
+    overridable mutable methods {
+      void(), b"GenerateBlock", mut_ptr(Byte), size_t();
+      void(), b"IncorporateEntropy", const_ptr(Byte), size_t();
+      byte(), b"GenerateByte";
+    }
+    overridable constant methods {
+      bool(), b"CanIncorporateEntropy";
+    }
+  });
+
+  check_bindings("overridable_rng", &rng);
+
+  let mut stream = Vec::new();
+  rng.write_trait(b"RandomNumberGenerator", &mut stream).unwrap();
+  check_golden("overridable_rng_trait.rs", &stream);
+
+  let bad = class!(b"BufferedTransformation" => {
+    overridable mutable methods {
+      void(), b"Attach", mut_ptr(Custom(b"BufferedTransformation"));
+    }
+  });
+
+  let mut ctx = gen::Context::new(Vec::new(), Vec::new());
+  assert!(bad.generate_bindings(&mut ctx).is_err());
+}
+
+#[test]
+fn operators() {
+  let integer = class!(b"Integer" => {
+    constructors {
+      b"";
+    }
+    operators with traits {
+      owned(Custom(b"Integer")), Add, const_ref(Custom(b"Integer"));
+      owned(Custom(b"Integer")), Shl, size_t();
+      owned(Custom(b"Integer")), Neg;
+      bool(), Eq, const_ref(Custom(b"Integer"));
+      bool(), Lt, const_ref(Custom(b"Integer"));
+      void(), AddAssign, const_ref(Custom(b"Integer"));
+      void(), ShrAssign, size_t();
+    }
+    operators {
+      owned(Custom(b"Integer")), Mul => b"mul_word", word();
+    }
+  });
+
+  check_bindings("operators_integer", &integer);
+
+  let mut stream = Vec::new();
+  integer.write_struct(b"Integer", &mut stream).unwrap();
+  check_golden("operators_integer_struct.rs", &stream);
+
+  let bad = class!(b"Integer" => {
+    operators {
+      owned(Custom(b"Integer")), Eq, const_ref(Custom(b"Integer"));
+    }
+  });
+
+  let mut ctx = gen::Context::new(Vec::new(), Vec::new());
+  assert!(bad.generate_bindings(&mut ctx).is_err());
+
+  let no_trait = class!(b"Integer" => {
+    operators with traits {
+      bool(), Eq, const_ref(Custom(b"PolynomialMod2"));
+    }
+  });
+
+  assert!(no_trait.write_struct(b"Integer", &mut Vec::new()).is_err());
+}
+
+#[test]
+fn enums_and_constants() {
+  let integer = class!(b"Integer" => {
+    enums {
+      b"Signedness" => b"UNSIGNED", b"SIGNED";
+    }
+    constant methods {
+      size_t(), b"MinEncodedSize", cpp_enum(b"Integer::Signedness");
+      void(),   b"Encode", mut_ptr(Byte), size_t(), cpp_enum(b"Integer::Signedness");
+    }
+  });
+  let sha256 = class!(b"SHA256" => {
+    constants {
+      uint(), b"DIGESTSIZE";
+      int(),  b"BLOCKSIZE";
+    }
+  });
+
+  check_bindings("enums_integer", &integer);
+
+  let mut stream = Vec::new();
+  integer.write_struct(b"Integer", &mut stream).unwrap();
+  check_golden("enums_integer_struct.rs", &stream);
+
+  let mut stream = Vec::new();
+  gen::probe::generate_probe_prelude(&mut stream).unwrap();
+  integer.generate_probe(&mut stream).unwrap();
+  sha256.generate_probe(&mut stream).unwrap();
+  gen::probe::generate_probe_end(&mut stream).unwrap();
+  check_golden("probe.cpp", &stream);
+
+  let values = gen::probe::Values::parse("Integer UNSIGNED 0\n\
+                                          Integer SIGNED 1\n\
+                                          SHA256 DIGESTSIZE 32\n\
+                                          SHA256 BLOCKSIZE 64\n").unwrap();
+
+  let mut stream = Vec::new();
+  integer.write_consts(&values, &mut stream).unwrap();
+  sha256.write_consts(&values, &mut stream).unwrap();
+  check_golden("consts.rs", &stream);
+
+  let missing = gen::probe::Values::parse("Integer UNSIGNED 0\n").unwrap();
+  assert!(integer.write_consts(&missing, &mut Vec::new()).is_err());
+
+  let same = gen::probe::Values::parse("Integer UNSIGNED 0\nInteger SIGNED 0\n").unwrap();
+  assert!(integer.write_consts(&same, &mut Vec::new()).is_err());
+
+  let bad = class!(b"Integer" => {
+    constants {
+      double(), b"EPSILON";
+    }
+  });
+  assert!(bad.generate_probe(&mut Vec::new()).is_err());
+}
+
+#[test]
+fn strings() {
+  let store = class!(b"StringStore" => {
+    constructors {
+      b"from_string", const_ref(StdString);
+    }
+  });
+  let ht = class!(b"HashTransformation" => {
+    constant methods {
+      utf8_string(), b"AlgorithmName";
+    }
+    mutable methods {
+      std_string(), b"CalculateDigestString", const_ref(StdString);
+    }
+  });
+
+  let mut ctx = gen::Context::with_c_header(Vec::new(), Vec::new(), Vec::new());
+  store.generate_bindings(&mut ctx).unwrap();
+  ht.generate_bindings(&mut ctx).unwrap();
+  check_golden("strings.cpp", &ctx.cpp_stream);
+  check_golden("strings.rs", &ctx.rs_binding_stream);
+  check_golden("strings.h", &ctx.c_header_stream);
+
+  let mut stream = Vec::new();
+  store.write_struct(b"StringStore", &mut stream).unwrap();
+  ht.write_struct(b"Hash", &mut stream).unwrap();
+  check_golden("strings_struct.rs", &stream);
+
+  let bad = class!(b"StringSink" => {
+    constructors {
+      b"", mut_ref(StdString);
+    }
+  });
+
+  let mut ctx = gen::Context::new(Vec::new(), Vec::new());
+  assert!(bad.generate_bindings(&mut ctx).is_err());
+
+  let bad = class!(b"RandomNumberGenerator" => {
+    overridable constant methods {
+      utf8_string(), b"AlgorithmName";
+    }
+  });
+
+  let mut ctx = gen::Context::new(Vec::new(), Vec::new());
+  assert!(bad.generate_bindings(&mut ctx).is_err());
+}
+
+#[test]
+fn c_header() {
+  let mut header = Vec::new();
+  gen::generate_c_header_prelude(&mut header).unwrap();
+
+  {
+    let mut ctx = gen::Context::with_c_header(Vec::new(), Vec::new(), &mut header);
+
+    let integer = class!(vec![b"CryptoPP"], b"Integer" => {
+      constructors {
+        b"";
+        b"random", mut_ref(Custom(b"RandomNumberGenerator")), size_t();
+      }
+      constant methods {
+        bool(),   b"IsNegative";
+        word32(), b"GetBits", size_t(), size_t();
+        void(),   b"Encode", mut_ptr(Byte), size_t();
+        owned(Custom(b"CryptoPP::Integer")), b"Squared";
+      }
+      static methods {
+        owned(Custom(b"CryptoPP::Integer")), b"Power2", size_t();
+      }
+    });
+    integer.generate_bindings(&mut ctx).unwrap();
+
+    let rng = class!(b"RandomNumberGenerator" => {
+      bases {
+        gen::class_path(vec![], b"Algorithm");
+      }
+      overridable mutable methods {
+        void(), b"GenerateBlock", mut_ptr(Byte), size_t();
+      }
+    });
+    rng.generate_bindings(&mut ctx).unwrap();
+
+    let cryptopp = namespace!(vec![b"CryptoPP"] => {
+      functions {
+        owned(Custom(b"CryptoPP::Integer")), b"a_exp_b_mod_c",
+          const_ref(Custom(b"CryptoPP::Integer")),
+          const_ref(Custom(b"CryptoPP::Integer")),
+          const_ref(Custom(b"CryptoPP::Integer"));
+      }
+    });
+    cryptopp.generate_bindings(&mut ctx).unwrap();
+  }
+
+  gen::generate_c_header_end(&mut header).unwrap();
+  check_golden("rustcryptopp.h", &header);
+}
+
+#[test]
+fn spec_files() {
+  let spec = gen::spec::Spec::parse(r#"
+    headers = ["cryptopp/integer.h", "cryptopp/cryptlib.h"]
+
+    [[class]]
+    name = "Integer"
+    constructors = [{ name = "" }]
+    methods = [
+      { name = "Squared", returns = "Integer", const = true },
+      { name = "Plus", returns = "Integer", args = ["const Integer&"], const = true },
+    ]
+    static_methods = [
+      { name = "Power2", returns = "Integer", args = ["size_t"] },
+      { name = "Gcd", returns = "Integer", args = ["Integer", "Integer"] },
+    ]
+    functions = [
+      { name = "a_exp_b_mod_c", returns = "Integer", args = ["const Integer&", "const Integer&", "const Integer&"] },
+    ]
+
+    [[class]]
+    name = "RandomNumberGenerator"
+    trait = "RandomNumberGenerator"
+    bases = ["Algorithm"]
+    overridable_methods = [
+      { name = "GenerateBlock", args = ["byte*", "size_t"] },
+      { name = "IncorporateEntropy", args = ["const byte*", "size_t"] },
+      { name = "GenerateByte", returns = "byte" },
+      { name = "CanIncorporateEntropy", returns = "bool", const = true },
+    ]
+  "#).unwrap();
+
+  assert_eq!(spec.headers, vec!["cryptopp/integer.h", "cryptopp/cryptlib.h"]);
+
+  let integer = spec.classes[0].named();
+  check_bindings("owned_returns_integer", &integer[0]);
+
+  let rng = spec.classes[1].named();
+  check_bindings("overridable_rng", &rng[0]);
+
+  let mut stream = Vec::new();
+  rng[0].write_trait(spec.classes[1].trait_name.unwrap(), &mut stream).unwrap();
+  check_golden("overridable_rng_trait.rs", &stream);
+
+  let spec = gen::spec::Spec::parse(r#"
+    [[class]]
+    name = "Integer"
+    struct = "Integer"
+    constructors = [{ name = "" }]
+    operators = [
+      { op = "+", returns = "Integer", args = ["const Integer&"], impl = true },
+      { op = "<<", returns = "Integer", args = ["size_t"], impl = true },
+      { op = "-", returns = "Integer", impl = true },
+      { op = "==", returns = "bool", args = ["const Integer&"], impl = true },
+      { op = "<", returns = "bool", args = ["const Integer&"], impl = true },
+      { op = "+=", args = ["const Integer&"], impl = true },
+      { op = ">>=", args = ["size_t"], impl = true },
+      { op = "*", alias = "mul_word", returns = "Integer", args = ["word"] },
+    ]
+  "#).unwrap();
+
+  let integer = spec.classes[0].named();
+  check_bindings("operators_integer", &integer[0]);
+
+  let mut stream = Vec::new();
+  integer[0].write_struct(spec.classes[0].struct_name.unwrap(), &mut stream).unwrap();
+  check_golden("operators_integer_struct.rs", &stream);
+
+  let spec = gen::spec::Spec::parse(r#"
+    headers = ["cryptopp/integer.h", "cryptopp/sha.h"]
+
+    [[class]]
+    name = "Integer"
+    enums = [{ name = "Signedness", variants = ["UNSIGNED", "SIGNED"] }]
+
+    [[class]]
+    name = "SHA256"
+    constants = [{ name = "DIGESTSIZE", type = "unsigned int" }, { name = "BLOCKSIZE", type = "int" }]
+  "#).unwrap();
+
+  let mut stream = Vec::new();
+  spec.generate_probe(&mut stream).unwrap();
+  let probe = String::from_utf8(stream).unwrap();
+  assert!(probe.starts_with("#include <cryptopp/integer.h>\n\
+                             #include <cryptopp/sha.h>\n\
+                             \n\
+                             using namespace CryptoPP;\n\
+                             \n"));
+
+  let mut expected = Vec::new();
+  File::open(golden_path("probe.cpp")).unwrap().read_to_end(&mut expected).unwrap();
+  assert!(probe.ends_with(&*String::from_utf8(expected).unwrap()));
+}
+
+#[test]
+fn preludes() {
+  let mut stream = Vec::new();
+  gen::generate_prelude(&mut stream).unwrap();
+  check_golden("prelude.rs", &stream);
+
+  let mut stream = Vec::new();
+  gen::generate_cpp_prelude(&mut stream).unwrap();
+  check_golden("prelude.cpp", &stream);
+
+  let mut stream = Vec::new();
+  gen::generate_c_header_prelude(&mut stream).unwrap();
+  gen::generate_c_header_end(&mut stream).unwrap();
+  check_golden("prelude.h", &stream);
+}
@@ -0,0 +1,25 @@
+extern "C"
+SHA3_256 * new_SHA3__256(rcpp_exception* exc) {
+  try {
+    return new SHA3_256();
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_SHA3__256(SHA3_256* ctx) {
+  delete ctx;
+}
+
+extern "C"
+HashTransformation* upcast_SHA3__256_HashTransformation(SHA3_256* ctx) {
+  return static_cast<HashTransformation*>(ctx);
+}
+
+extern "C"
+Algorithm* upcast_SHA3__256_Algorithm(SHA3_256* ctx) {
+  return static_cast<Algorithm*>(ctx);
+}
+
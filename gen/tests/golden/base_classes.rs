@@ -0,0 +1,8 @@
+#[allow(non_camel_case_types)]
+pub enum SHA3__256 {}
+extern {
+  pub fn new_SHA3__256(exc: *mut Exception) -> *mut c_void;
+  pub fn del_SHA3__256(ctx: *mut c_void);
+  pub fn upcast_SHA3__256_HashTransformation(ctx: *mut c_void) -> *mut c_void;
+  pub fn upcast_SHA3__256_Algorithm(ctx: *mut c_void) -> *mut c_void;
+}
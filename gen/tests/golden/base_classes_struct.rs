@@ -0,0 +1,46 @@
+use std;
+
+pub struct Hash {
+  ctx: *mut c_void
+}
+impl Drop for Hash {
+  fn drop(&mut self) {
+    unsafe { cpp::del_SHA3__256(self.ctx) };
+  }
+}
+impl Hash {
+  pub fn try_new() -> ::Result<Hash> {
+    let ctx = try!(cpp::catch(|exc| unsafe { cpp::new_SHA3__256(exc) }));
+
+    Ok(Hash { ctx: ctx })
+  }
+
+  pub fn new() -> Hash {
+    Hash::try_new().unwrap()
+  }
+}
+
+impl cpp::CPPContext for Hash {
+  fn mut_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::SHA3__256> for Hash {
+  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::HashTransformation> for Hash {
+  fn mut_base_ctx(&self) -> *mut c_void {
+    unsafe { cpp::upcast_SHA3__256_HashTransformation(self.ctx) }
+  }
+}
+
+impl cpp::Upcast<cpp::Algorithm> for Hash {
+  fn mut_base_ctx(&self) -> *mut c_void {
+    unsafe { cpp::upcast_SHA3__256_Algorithm(self.ctx) }
+  }
+}
+
+impl std::default::Default for Hash {
+  fn default() -> Hash { Hash::new() }
+}
+
@@ -0,0 +1,15 @@
+extern "C"
+void del_HMAC_LSHA256_R(HMAC<SHA256>* ctx) {
+  delete ctx;
+}
+
+extern "C"
+MessageAuthenticationCode* upcast_HMAC_LSHA256_R_MessageAuthenticationCode(HMAC<SHA256>* ctx) {
+  return static_cast<MessageAuthenticationCode*>(ctx);
+}
+
+extern "C"
+SimpleKeyingInterface* upcast_HMAC_LSHA256_R_SimpleKeyingInterface(HMAC<SHA256>* ctx) {
+  return static_cast<SimpleKeyingInterface*>(ctx);
+}
+
@@ -0,0 +1,7 @@
+#[allow(non_camel_case_types)]
+pub enum HMAC_LSHA256_R {}
+extern {
+  pub fn del_HMAC_LSHA256_R(ctx: *mut c_void);
+  pub fn upcast_HMAC_LSHA256_R_MessageAuthenticationCode(ctx: *mut c_void) -> *mut c_void;
+  pub fn upcast_HMAC_LSHA256_R_SimpleKeyingInterface(ctx: *mut c_void) -> *mut c_void;
+}
@@ -0,0 +1,8 @@
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Signedness {
+  Unsigned = 0,
+  Signed = 1,
+}
+
+pub const DIGESTSIZE: ::libc::c_uint = 32;
+pub const BLOCKSIZE: ::libc::c_int = 64;
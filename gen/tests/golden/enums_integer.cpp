@@ -0,0 +1,24 @@
+extern "C"
+void del_Integer(Integer* ctx) {
+  delete ctx;
+}
+
+extern "C"
+size_t mth_Integer_MinEncodedSize(Integer const* ctx, Integer::Signedness arg0, rcpp_exception* exc) {
+  try {
+    return ctx->MinEncodedSize( arg0);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<size_t>();
+  }
+}
+
+extern "C"
+void mth_Integer_Encode(Integer const* ctx, byte* arg0, size_t arg1, Integer::Signedness arg2, rcpp_exception* exc) {
+  try {
+    ctx->Encode( arg0,  arg1,  arg2);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
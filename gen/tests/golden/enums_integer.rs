@@ -0,0 +1,7 @@
+#[allow(non_camel_case_types)]
+pub enum Integer {}
+extern {
+  pub fn mth_Integer_MinEncodedSize(ctx: *const c_void, arg0: c_int, exc: *mut Exception) -> size_t;
+  pub fn mth_Integer_Encode(ctx: *const c_void, arg0: *mut c_uchar, arg1: size_t, arg2: c_int, exc: *mut Exception);
+  pub fn del_Integer(ctx: *mut c_void);
+}
@@ -0,0 +1,36 @@
+use std;
+
+pub struct Integer {
+  ctx: *mut c_void
+}
+impl Drop for Integer {
+  fn drop(&mut self) {
+    unsafe { cpp::del_Integer(self.ctx) };
+  }
+}
+impl Integer {
+  pub fn min_encoded_size(&self, arg0: Signedness) -> ::Result<::libc::size_t> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_MinEncodedSize(self.ctx, arg0 as ::libc::c_int, exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn encode(&self, arg0: &mut [u8], arg1: Signedness) -> ::Result<()> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_Encode(self.ctx, arg0.as_mut_ptr(), arg0.len() as ::libc::size_t, arg1 as ::libc::c_int, exc)
+    }));
+
+    Ok(ret)
+  }
+}
+
+impl cpp::CPPContext for Integer {
+  fn mut_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::Integer> for Integer {
+  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }
+}
+
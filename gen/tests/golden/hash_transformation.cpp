@@ -0,0 +1,42 @@
+extern "C"
+void del_HashTransformation(HashTransformation* ctx) {
+  delete ctx;
+}
+
+extern "C"
+unsigned int mth_HashTransformation_DigestSize(HashTransformation const* ctx, rcpp_exception* exc) {
+  try {
+    return ctx->DigestSize();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<unsigned int>();
+  }
+}
+
+extern "C"
+void mth_HashTransformation_Update(HashTransformation* ctx, unsigned char const* arg0, size_t arg1, rcpp_exception* exc) {
+  try {
+    ctx->Update( arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
+extern "C"
+void mth_HashTransformation_Final(HashTransformation* ctx, unsigned char* arg0, rcpp_exception* exc) {
+  try {
+    ctx->Final( arg0);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
+extern "C"
+void mth_HashTransformation_Restart(HashTransformation* ctx, rcpp_exception* exc) {
+  try {
+    ctx->Restart();
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
@@ -0,0 +1,9 @@
+#[allow(non_camel_case_types)]
+pub enum HashTransformation {}
+extern {
+  pub fn mth_HashTransformation_DigestSize(ctx: *const c_void, exc: *mut Exception) -> c_uint;
+  pub fn mth_HashTransformation_Update(ctx: *mut c_void, arg0: *const c_uchar, arg1: size_t, exc: *mut Exception);
+  pub fn mth_HashTransformation_Final(ctx: *mut c_void, arg0: *mut c_uchar, exc: *mut Exception);
+  pub fn mth_HashTransformation_Restart(ctx: *mut c_void, exc: *mut Exception);
+  pub fn del_HashTransformation(ctx: *mut c_void);
+}
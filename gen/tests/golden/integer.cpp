@@ -0,0 +1,35 @@
+extern "C"
+CryptoPP::Integer * new_CryptoPP_SInteger(rcpp_exception* exc) {
+  try {
+    return new CryptoPP::Integer();
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+CryptoPP::Integer * new_copy_CryptoPP_SInteger(Integer const* arg0, rcpp_exception* exc) {
+  try {
+    return new CryptoPP::Integer( *arg0);
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+CryptoPP::Integer * new_from_long_CryptoPP_SInteger(long arg0, rcpp_exception* exc) {
+  try {
+    return new CryptoPP::Integer( arg0);
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_CryptoPP_SInteger(CryptoPP::Integer* ctx) {
+  delete ctx;
+}
+
@@ -0,0 +1,8 @@
+#[allow(non_camel_case_types)]
+pub enum CryptoPP_SInteger {}
+extern {
+  pub fn new_CryptoPP_SInteger(exc: *mut Exception) -> *mut c_void;
+  pub fn new_copy_CryptoPP_SInteger(arg0: *const c_void, exc: *mut Exception) -> *mut c_void;
+  pub fn new_from_long_CryptoPP_SInteger(arg0: c_long, exc: *mut Exception) -> *mut c_void;
+  pub fn del_CryptoPP_SInteger(ctx: *mut c_void);
+}
@@ -0,0 +1,14 @@
+extern "C"
+void del_HashTransformation(HashTransformation* ctx) {
+  delete ctx;
+}
+
+extern "C"
+void mth_HashTransformation_CalculateTruncatedDigest(HashTransformation* ctx, unsigned char* arg0, size_t arg1, unsigned char const* arg2, size_t arg3, rcpp_exception* exc) {
+  try {
+    ctx->CalculateTruncatedDigest( arg0,  arg1,  arg2,  arg3);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
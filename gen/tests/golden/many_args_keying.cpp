@@ -0,0 +1,14 @@
+extern "C"
+void del_SimpleKeyingInterface(SimpleKeyingInterface* ctx) {
+  delete ctx;
+}
+
+extern "C"
+void mth_SimpleKeyingInterface_SetKeyWithIV(SimpleKeyingInterface* ctx, unsigned char const* arg0, size_t arg1, unsigned char const* arg2, size_t arg3, rcpp_exception* exc) {
+  try {
+    ctx->SetKeyWithIV( arg0,  arg1,  arg2,  arg3);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
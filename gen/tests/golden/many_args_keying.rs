@@ -0,0 +1,6 @@
+#[allow(non_camel_case_types)]
+pub enum SimpleKeyingInterface {}
+extern {
+  pub fn mth_SimpleKeyingInterface_SetKeyWithIV(ctx: *mut c_void, arg0: *const c_uchar, arg1: size_t, arg2: *const c_uchar, arg3: size_t, exc: *mut Exception);
+  pub fn del_SimpleKeyingInterface(ctx: *mut c_void);
+}
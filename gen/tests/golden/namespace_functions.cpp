@@ -0,0 +1,29 @@
+extern "C"
+void fn_CryptoPP_OS_GenerateRandomBlock(bool arg0, byte* arg1, size_t arg2, rcpp_exception* exc) {
+  try {
+    CryptoPP::OS_GenerateRandomBlock( arg0,  arg1,  arg2);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
+extern "C"
+bool fn_CryptoPP_VerifyBufsEqual(byte const* arg0, byte const* arg1, size_t arg2, rcpp_exception* exc) {
+  try {
+    return CryptoPP::VerifyBufsEqual( arg0,  arg1,  arg2);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<bool>();
+  }
+}
+
+extern "C"
+int fn_CryptoPP_HeaderVersion(rcpp_exception* exc) {
+  try {
+    return CryptoPP::HeaderVersion();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<int>();
+  }
+}
+
@@ -0,0 +1,5 @@
+extern {
+  pub fn fn_CryptoPP_OS_GenerateRandomBlock(arg0: bool, arg1: *mut c_uchar, arg2: size_t, exc: *mut Exception);
+  pub fn fn_CryptoPP_VerifyBufsEqual(arg0: *const c_uchar, arg1: *const c_uchar, arg2: size_t, exc: *mut Exception) -> bool;
+  pub fn fn_CryptoPP_HeaderVersion(exc: *mut Exception) -> c_int;
+}
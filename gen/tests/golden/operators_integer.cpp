@@ -0,0 +1,93 @@
+extern "C"
+Integer * new_Integer(rcpp_exception* exc) {
+  try {
+    return new Integer();
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_Integer(Integer* ctx) {
+  delete ctx;
+}
+
+extern "C"
+Integer* op_Integer_add(Integer const* ctx, Integer const* arg0, rcpp_exception* exc) {
+  try {
+    return new Integer(*ctx + *arg0);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer*>();
+  }
+}
+
+extern "C"
+Integer* op_Integer_shl(Integer const* ctx, size_t arg0, rcpp_exception* exc) {
+  try {
+    return new Integer(*ctx << arg0);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer*>();
+  }
+}
+
+extern "C"
+Integer* op_Integer_neg(Integer const* ctx, rcpp_exception* exc) {
+  try {
+    return new Integer(-*ctx);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer*>();
+  }
+}
+
+extern "C"
+bool op_Integer_eq(Integer const* ctx, Integer const* arg0, rcpp_exception* exc) {
+  try {
+    return *ctx == *arg0;
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<bool>();
+  }
+}
+
+extern "C"
+bool op_Integer_lt(Integer const* ctx, Integer const* arg0, rcpp_exception* exc) {
+  try {
+    return *ctx < *arg0;
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<bool>();
+  }
+}
+
+extern "C"
+void op_Integer_add_assign(Integer* ctx, Integer const* arg0, rcpp_exception* exc) {
+  try {
+    *ctx += *arg0;
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
+extern "C"
+void op_Integer_shr_assign(Integer* ctx, size_t arg0, rcpp_exception* exc) {
+  try {
+    *ctx >>= arg0;
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
+extern "C"
+Integer* op_Integer_mul_word(Integer const* ctx, word arg0, rcpp_exception* exc) {
+  try {
+    return new Integer(*ctx * arg0);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer*>();
+  }
+}
+
@@ -0,0 +1,14 @@
+#[allow(non_camel_case_types)]
+pub enum Integer {}
+extern {
+  pub fn op_Integer_add(ctx: *const c_void, arg0: *const c_void, exc: *mut Exception) -> *mut c_void;
+  pub fn op_Integer_shl(ctx: *const c_void, arg0: size_t, exc: *mut Exception) -> *mut c_void;
+  pub fn op_Integer_neg(ctx: *const c_void, exc: *mut Exception) -> *mut c_void;
+  pub fn op_Integer_eq(ctx: *const c_void, arg0: *const c_void, exc: *mut Exception) -> bool;
+  pub fn op_Integer_lt(ctx: *const c_void, arg0: *const c_void, exc: *mut Exception) -> bool;
+  pub fn op_Integer_add_assign(ctx: *mut c_void, arg0: *const c_void, exc: *mut Exception);
+  pub fn op_Integer_shr_assign(ctx: *mut c_void, arg0: size_t, exc: *mut Exception);
+  pub fn op_Integer_mul_word(ctx: *const c_void, arg0: size_t, exc: *mut Exception) -> *mut c_void;
+  pub fn new_Integer(exc: *mut Exception) -> *mut c_void;
+  pub fn del_Integer(ctx: *mut c_void);
+}
@@ -0,0 +1,152 @@
+use std;
+
+pub struct Integer {
+  ctx: *mut c_void
+}
+impl Drop for Integer {
+  fn drop(&mut self) {
+    unsafe { cpp::del_Integer(self.ctx) };
+  }
+}
+impl Integer {
+  pub fn try_new() -> ::Result<Integer> {
+    let ctx = try!(cpp::catch(|exc| unsafe { cpp::new_Integer(exc) }));
+
+    Ok(Integer { ctx: ctx })
+  }
+
+  pub fn new() -> Integer {
+    Integer::try_new().unwrap()
+  }
+
+  pub fn try_add(&self, arg0: &cpp::Upcast<cpp::Integer>) -> ::Result<Integer> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::op_Integer_add(self.ctx, cpp::Upcast::base_ctx(arg0), exc)
+    }));
+
+    Ok(Integer { ctx: ret })
+  }
+
+  pub fn try_shl(&self, arg0: ::libc::size_t) -> ::Result<Integer> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::op_Integer_shl(self.ctx, arg0, exc)
+    }));
+
+    Ok(Integer { ctx: ret })
+  }
+
+  pub fn try_neg(&self) -> ::Result<Integer> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::op_Integer_neg(self.ctx, exc)
+    }));
+
+    Ok(Integer { ctx: ret })
+  }
+
+  pub fn try_eq(&self, arg0: &cpp::Upcast<cpp::Integer>) -> ::Result<bool> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::op_Integer_eq(self.ctx, cpp::Upcast::base_ctx(arg0), exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn try_lt(&self, arg0: &cpp::Upcast<cpp::Integer>) -> ::Result<bool> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::op_Integer_lt(self.ctx, cpp::Upcast::base_ctx(arg0), exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn try_add_assign(&mut self, arg0: &cpp::Upcast<cpp::Integer>) -> ::Result<()> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::op_Integer_add_assign(self.ctx, cpp::Upcast::base_ctx(arg0), exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn try_shr_assign(&mut self, arg0: ::libc::size_t) -> ::Result<()> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::op_Integer_shr_assign(self.ctx, arg0, exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn try_mul_word(&self, arg0: ::libc::size_t) -> ::Result<Integer> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::op_Integer_mul_word(self.ctx, arg0, exc)
+    }));
+
+    Ok(Integer { ctx: ret })
+  }
+}
+
+impl cpp::CPPContext for Integer {
+  fn mut_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::Integer> for Integer {
+  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl std::default::Default for Integer {
+  fn default() -> Integer { Integer::new() }
+}
+
+impl<'a, 'b> std::ops::Add<&'b Integer> for &'a Integer {
+  type Output = Integer;
+
+  fn add(self, rhs: &'b Integer) -> Integer {
+    self.try_add(rhs).unwrap()
+  }
+}
+
+impl<'a> std::ops::Shl<::libc::size_t> for &'a Integer {
+  type Output = Integer;
+
+  fn shl(self, rhs: ::libc::size_t) -> Integer {
+    self.try_shl(rhs).unwrap()
+  }
+}
+
+impl<'a> std::ops::Neg for &'a Integer {
+  type Output = Integer;
+
+  fn neg(self) -> Integer {
+    self.try_neg().unwrap()
+  }
+}
+
+impl std::cmp::PartialEq for Integer {
+  fn eq(&self, other: &Integer) -> bool {
+    self.try_eq(other).unwrap()
+  }
+}
+
+impl std::cmp::PartialOrd for Integer {
+  fn partial_cmp(&self, other: &Integer) -> Option<std::cmp::Ordering> {
+    if self.try_lt(other).unwrap() {
+      Some(std::cmp::Ordering::Less)
+    } else if other.try_lt(self).unwrap() {
+      Some(std::cmp::Ordering::Greater)
+    } else {
+      Some(std::cmp::Ordering::Equal)
+    }
+  }
+}
+
+impl<'b> std::ops::AddAssign<&'b Integer> for Integer {
+  fn add_assign(&mut self, rhs: &'b Integer) {
+    self.try_add_assign(rhs).unwrap()
+  }
+}
+
+impl std::ops::ShrAssign<::libc::size_t> for Integer {
+  fn shr_assign(&mut self, rhs: ::libc::size_t) {
+    self.try_shr_assign(rhs).unwrap()
+  }
+}
+
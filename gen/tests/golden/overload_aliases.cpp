@@ -0,0 +1,42 @@
+extern "C"
+void del_Integer(Integer* ctx) {
+  delete ctx;
+}
+
+extern "C"
+void mth_Integer_Encode(Integer const* ctx, byte* arg0, size_t arg1, rcpp_exception* exc) {
+  try {
+    ctx->Encode( arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
+extern "C"
+void mth_Integer_EncodeToSink(Integer const* ctx, BufferedTransformation* arg0, size_t arg1, rcpp_exception* exc) {
+  try {
+    ctx->Encode( *arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
+extern "C"
+size_t mth_Integer_MinEncodedSize(Integer const* ctx, rcpp_exception* exc) {
+  try {
+    return ctx->MinEncodedSize();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<size_t>();
+  }
+}
+
+extern "C"
+void mth_Integer_DecodeBytes(Integer* ctx, byte const* arg0, size_t arg1, rcpp_exception* exc) {
+  try {
+    ctx->Decode( arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
@@ -0,0 +1,9 @@
+#[allow(non_camel_case_types)]
+pub enum Integer {}
+extern {
+  pub fn mth_Integer_Encode(ctx: *const c_void, arg0: *mut c_uchar, arg1: size_t, exc: *mut Exception);
+  pub fn mth_Integer_EncodeToSink(ctx: *const c_void, arg0: *mut c_void, arg1: size_t, exc: *mut Exception);
+  pub fn mth_Integer_MinEncodedSize(ctx: *const c_void, exc: *mut Exception) -> size_t;
+  pub fn mth_Integer_DecodeBytes(ctx: *mut c_void, arg0: *const c_uchar, arg1: size_t, exc: *mut Exception);
+  pub fn del_Integer(ctx: *mut c_void);
+}
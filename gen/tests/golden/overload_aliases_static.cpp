@@ -0,0 +1,25 @@
+extern "C"
+void del_SHA1(SHA1* ctx) {
+  delete ctx;
+}
+
+extern "C"
+char const* smth_SHA1_Name(rcpp_exception* exc) {
+  try {
+    return SHA1::StaticAlgorithmName();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<char const*>();
+  }
+}
+
+extern "C"
+bool fn_ConstantTimeEq(byte const* arg0, byte const* arg1, size_t arg2, rcpp_exception* exc) {
+  try {
+    return VerifyBufsEqual( arg0,  arg1,  arg2);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<bool>();
+  }
+}
+
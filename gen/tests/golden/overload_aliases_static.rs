@@ -0,0 +1,7 @@
+#[allow(non_camel_case_types)]
+pub enum SHA1 {}
+extern {
+  pub fn del_SHA1(ctx: *mut c_void);
+  pub fn smth_SHA1_Name(exc: *mut Exception) -> *const c_char;
+  pub fn fn_ConstantTimeEq(arg0: *const c_uchar, arg1: *const c_uchar, arg2: size_t, exc: *mut Exception) -> bool;
+}
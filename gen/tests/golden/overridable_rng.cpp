@@ -0,0 +1,68 @@
+extern "C"
+void del_RandomNumberGenerator(RandomNumberGenerator* ctx) {
+  delete ctx;
+}
+
+extern "C"
+Algorithm* upcast_RandomNumberGenerator_Algorithm(RandomNumberGenerator* ctx) {
+  return static_cast<Algorithm*>(ctx);
+}
+
+struct rcpp_vtable_RandomNumberGenerator {
+  void (*GenerateBlock)(void* self, byte* arg0, size_t arg1, rcpp_exception* exc);
+  void (*IncorporateEntropy)(void* self, byte const* arg0, size_t arg1, rcpp_exception* exc);
+  byte (*GenerateByte)(void* self, rcpp_exception* exc);
+  bool (*CanIncorporateEntropy)(void* self, rcpp_exception* exc);
+  void (*drop)(void* self);
+};
+
+class rcpp_rust_RandomNumberGenerator : public RandomNumberGenerator {
+public:
+  rcpp_rust_RandomNumberGenerator(void* self, rcpp_vtable_RandomNumberGenerator const* vtable)
+    : rcpp_self(self), rcpp_vtable(*vtable) {}
+
+  ~rcpp_rust_RandomNumberGenerator() {
+    rcpp_vtable.drop(rcpp_self);
+  }
+
+  void GenerateBlock(byte* arg0, size_t arg1) {
+    rcpp_exception exc = { RCPP_EXC_NONE, NULL };
+    rcpp_vtable.GenerateBlock(rcpp_self, arg0, arg1, &exc);
+    rcpp_rethrow(&exc);
+  }
+
+  void IncorporateEntropy(byte const* arg0, size_t arg1) {
+    rcpp_exception exc = { RCPP_EXC_NONE, NULL };
+    rcpp_vtable.IncorporateEntropy(rcpp_self, arg0, arg1, &exc);
+    rcpp_rethrow(&exc);
+  }
+
+  byte GenerateByte() {
+    rcpp_exception exc = { RCPP_EXC_NONE, NULL };
+    byte ret = rcpp_vtable.GenerateByte(rcpp_self, &exc);
+    rcpp_rethrow(&exc);
+    return ret;
+  }
+
+  bool CanIncorporateEntropy() const {
+    rcpp_exception exc = { RCPP_EXC_NONE, NULL };
+    bool ret = rcpp_vtable.CanIncorporateEntropy(rcpp_self, &exc);
+    rcpp_rethrow(&exc);
+    return ret;
+  }
+
+private:
+  void* rcpp_self;
+  rcpp_vtable_RandomNumberGenerator rcpp_vtable;
+};
+
+extern "C"
+RandomNumberGenerator* new_rust_RandomNumberGenerator(void* self, rcpp_vtable_RandomNumberGenerator const* vtable, rcpp_exception* exc) {
+  try {
+    return new rcpp_rust_RandomNumberGenerator(self, vtable);
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
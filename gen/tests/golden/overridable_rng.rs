@@ -0,0 +1,16 @@
+#[allow(non_camel_case_types)]
+pub enum RandomNumberGenerator {}
+#[repr(C)]
+#[allow(non_snake_case, non_camel_case_types)]
+pub struct vtable_RandomNumberGenerator {
+  pub GenerateBlock: extern "C" fn(this: *mut c_void, arg0: *mut c_uchar, arg1: size_t, exc: *mut Exception),
+  pub IncorporateEntropy: extern "C" fn(this: *mut c_void, arg0: *const c_uchar, arg1: size_t, exc: *mut Exception),
+  pub GenerateByte: extern "C" fn(this: *mut c_void, exc: *mut Exception) -> c_uchar,
+  pub CanIncorporateEntropy: extern "C" fn(this: *mut c_void, exc: *mut Exception) -> bool,
+  pub drop: extern "C" fn(this: *mut c_void)
+}
+extern {
+  pub fn del_RandomNumberGenerator(ctx: *mut c_void);
+  pub fn upcast_RandomNumberGenerator_Algorithm(ctx: *mut c_void) -> *mut c_void;
+  pub fn new_rust_RandomNumberGenerator(this: *mut c_void, vtable: *const vtable_RandomNumberGenerator, exc: *mut Exception) -> *mut c_void;
+}
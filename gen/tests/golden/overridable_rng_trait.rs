@@ -0,0 +1,95 @@
+pub trait RandomNumberGenerator {
+  fn generate_block(&mut self, arg0: &mut [u8]) -> ::Result<()>;
+  fn incorporate_entropy(&mut self, arg0: &[u8]) -> ::Result<()>;
+  fn generate_byte(&mut self) -> ::Result<::libc::c_uchar>;
+  fn can_incorporate_entropy(&self) -> ::Result<bool>;
+}
+
+#[allow(non_snake_case)]
+mod trampolines_RandomNumberGenerator {
+  #[allow(unused_imports)]
+  use libc::{c_void, c_uchar, c_char, c_long, c_longlong, size_t, c_uint, c_int,
+             c_double};
+
+  use cpp;
+  use super::RandomNumberGenerator as Trait;
+
+  pub fn vtable<T: Trait>() -> cpp::vtable_RandomNumberGenerator {
+    cpp::vtable_RandomNumberGenerator {
+      GenerateBlock: GenerateBlock::<T>,
+      IncorporateEntropy: IncorporateEntropy::<T>,
+      GenerateByte: GenerateByte::<T>,
+      CanIncorporateEntropy: CanIncorporateEntropy::<T>,
+      drop: drop::<T>
+    }
+  }
+
+  extern "C" fn GenerateBlock<T: Trait>(this: *mut c_void, arg0: *mut c_uchar, arg1: size_t, exc: *mut cpp::Exception) {
+    let this = unsafe { &mut *(this as *mut T) };
+    let arg0 = unsafe { cpp::slice_mut(arg0, arg1) };
+    cpp::callback(exc, move || this.generate_block(arg0))
+  }
+
+  extern "C" fn IncorporateEntropy<T: Trait>(this: *mut c_void, arg0: *const c_uchar, arg1: size_t, exc: *mut cpp::Exception) {
+    let this = unsafe { &mut *(this as *mut T) };
+    let arg0 = unsafe { cpp::slice(arg0, arg1) };
+    cpp::callback(exc, move || this.incorporate_entropy(arg0))
+  }
+
+  extern "C" fn GenerateByte<T: Trait>(this: *mut c_void, exc: *mut cpp::Exception) -> c_uchar {
+    let this = unsafe { &mut *(this as *mut T) };
+    cpp::callback(exc, move || this.generate_byte())
+  }
+
+  extern "C" fn CanIncorporateEntropy<T: Trait>(this: *mut c_void, exc: *mut cpp::Exception) -> bool {
+    let this = unsafe { &*(this as *const T) };
+    cpp::callback(exc, move || this.can_incorporate_entropy())
+  }
+
+  pub extern "C" fn drop<T: Trait>(this: *mut c_void) {
+    unsafe { ::std::mem::drop(Box::from_raw(this as *mut T)) };
+  }
+}
+
+pub struct RustRandomNumberGenerator {
+  ctx: *mut c_void
+}
+impl Drop for RustRandomNumberGenerator {
+  fn drop(&mut self) {
+    unsafe { cpp::del_RandomNumberGenerator(self.ctx) };
+  }
+}
+impl RustRandomNumberGenerator {
+  pub fn try_new<T: RandomNumberGenerator + 'static>(imp: T) -> ::Result<RustRandomNumberGenerator> {
+    let this = Box::into_raw(Box::new(imp)) as *mut c_void;
+    let vtable = trampolines_RandomNumberGenerator::vtable::<T>();
+    let ctx = match cpp::catch(|exc| unsafe { cpp::new_rust_RandomNumberGenerator(this, &vtable, exc) }) {
+      Ok(ctx) => ctx,
+      Err(e)  => {
+        (vtable.drop)(this);
+        return Err(::Error::from(e));
+      }
+    };
+
+    Ok(RustRandomNumberGenerator { ctx: ctx })
+  }
+
+  pub fn new<T: RandomNumberGenerator + 'static>(imp: T) -> RustRandomNumberGenerator {
+    RustRandomNumberGenerator::try_new(imp).unwrap()
+  }
+}
+
+impl cpp::CPPContext for RustRandomNumberGenerator {
+  fn mut_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::RandomNumberGenerator> for RustRandomNumberGenerator {
+  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::Algorithm> for RustRandomNumberGenerator {
+  fn mut_base_ctx(&self) -> *mut c_void {
+    unsafe { cpp::upcast_RandomNumberGenerator_Algorithm(self.ctx) }
+  }
+}
+
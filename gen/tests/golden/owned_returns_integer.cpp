@@ -0,0 +1,65 @@
+extern "C"
+Integer * new_Integer(rcpp_exception* exc) {
+  try {
+    return new Integer();
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_Integer(Integer* ctx) {
+  delete ctx;
+}
+
+extern "C"
+Integer* mth_Integer_Squared(Integer const* ctx, rcpp_exception* exc) {
+  try {
+    return new Integer(ctx->Squared());
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer*>();
+  }
+}
+
+extern "C"
+Integer* mth_Integer_Plus(Integer const* ctx, Integer const* arg0, rcpp_exception* exc) {
+  try {
+    return new Integer(ctx->Plus( *arg0));
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer*>();
+  }
+}
+
+extern "C"
+Integer* smth_Integer_Power2(size_t arg0, rcpp_exception* exc) {
+  try {
+    return new Integer(Integer::Power2( arg0));
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer*>();
+  }
+}
+
+extern "C"
+Integer* smth_Integer_Gcd(Integer* arg0, Integer* arg1, rcpp_exception* exc) {
+  try {
+    return new Integer(Integer::Gcd( *arg0,  *arg1));
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer*>();
+  }
+}
+
+extern "C"
+Integer* fn_a_exp_b_mod_c(Integer const* arg0, Integer const* arg1, Integer const* arg2, rcpp_exception* exc) {
+  try {
+    return new Integer(a_exp_b_mod_c( *arg0,  *arg1,  *arg2));
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer*>();
+  }
+}
+
@@ -0,0 +1,11 @@
+#[allow(non_camel_case_types)]
+pub enum Integer {}
+extern {
+  pub fn mth_Integer_Squared(ctx: *const c_void, exc: *mut Exception) -> *mut c_void;
+  pub fn mth_Integer_Plus(ctx: *const c_void, arg0: *const c_void, exc: *mut Exception) -> *mut c_void;
+  pub fn new_Integer(exc: *mut Exception) -> *mut c_void;
+  pub fn del_Integer(ctx: *mut c_void);
+  pub fn smth_Integer_Power2(arg0: size_t, exc: *mut Exception) -> *mut c_void;
+  pub fn smth_Integer_Gcd(arg0: *mut c_void, arg1: *mut c_void, exc: *mut Exception) -> *mut c_void;
+  pub fn fn_a_exp_b_mod_c(arg0: *const c_void, arg1: *const c_void, arg2: *const c_void, exc: *mut Exception) -> *mut c_void;
+}
@@ -0,0 +1,15 @@
+extern "C"
+void del_Algorithm(Algorithm* ctx) {
+  delete ctx;
+}
+
+extern "C"
+std::string* mth_Algorithm_AlgorithmName(Algorithm const* ctx, rcpp_exception* exc) {
+  try {
+    return new std::string(ctx->AlgorithmName());
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<std::string*>();
+  }
+}
+
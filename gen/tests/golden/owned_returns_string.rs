@@ -0,0 +1,6 @@
+#[allow(non_camel_case_types)]
+pub enum Algorithm {}
+extern {
+  pub fn mth_Algorithm_AlgorithmName(ctx: *const c_void, exc: *mut Exception) -> *mut c_void;
+  pub fn del_Algorithm(ctx: *mut c_void);
+}
@@ -0,0 +1,147 @@
+#include <cstdlib>
+#include <cstring>
+#include <exception>
+#include <new>
+#include <stdexcept>
+#include <string>
+
+enum {
+  RCPP_EXC_NONE = 0,
+  RCPP_EXC_NOT_IMPLEMENTED = 1,
+  RCPP_EXC_INVALID_ARGUMENT = 2,
+  RCPP_EXC_CANNOT_FLUSH = 3,
+  RCPP_EXC_DATA_INTEGRITY_CHECK_FAILED = 4,
+  RCPP_EXC_INVALID_DATA_FORMAT = 5,
+  RCPP_EXC_IO_ERROR = 6,
+  RCPP_EXC_OTHER_ERROR = 7,
+  RCPP_EXC_BAD_ALLOC = 8,
+  RCPP_EXC_STD_EXCEPTION = 9,
+  RCPP_EXC_UNKNOWN = 10,
+  RCPP_EXC_RUST_PANIC = 11,
+};
+
+// `word` is bound as `size_t` on the rust side.
+typedef char rcpp_word_is_size_t[sizeof(CryptoPP::word) == sizeof(size_t) ? 1 : -1];
+
+struct rcpp_exception {
+  int code;
+  char* what;
+};
+
+// a std::string crossing a shim. an argument borrows the bytes of the
+// caller, a returned string is malloc'd and released by the caller.
+struct rcpp_string {
+  char* data;
+  size_t len;
+};
+
+template <typename T>
+static T rcpp_default() {
+  return T();
+}
+
+static std::string rcpp_from_string(rcpp_string s) {
+  return s.len > 0 ? std::string(s.data, s.len) : std::string();
+}
+
+static rcpp_string rcpp_to_string(const std::string& s) {
+  rcpp_string copy = { NULL, s.size() };
+  if (copy.len > 0) {
+    copy.data = static_cast<char*>(std::malloc(copy.len));
+    if (copy.data == NULL) {
+      throw std::bad_alloc();
+    }
+    std::memcpy(copy.data, s.data(), copy.len);
+  }
+  return copy;
+}
+
+static char* rcpp_copy_what(const char* what) {
+  size_t len = std::strlen(what);
+  char* copy = static_cast<char*>(std::malloc(len + 1));
+  if (copy != NULL) {
+    std::memcpy(copy, what, len + 1);
+  }
+  return copy;
+}
+
+static int rcpp_error_type_code(CryptoPP::Exception::ErrorType t) {
+  switch (t) {
+    case CryptoPP::Exception::NOT_IMPLEMENTED:
+      return RCPP_EXC_NOT_IMPLEMENTED;
+    case CryptoPP::Exception::INVALID_ARGUMENT:
+      return RCPP_EXC_INVALID_ARGUMENT;
+    case CryptoPP::Exception::CANNOT_FLUSH:
+      return RCPP_EXC_CANNOT_FLUSH;
+    case CryptoPP::Exception::DATA_INTEGRITY_CHECK_FAILED:
+      return RCPP_EXC_DATA_INTEGRITY_CHECK_FAILED;
+    case CryptoPP::Exception::INVALID_DATA_FORMAT:
+      return RCPP_EXC_INVALID_DATA_FORMAT;
+    case CryptoPP::Exception::IO_ERROR:
+      return RCPP_EXC_IO_ERROR;
+    default:
+      return RCPP_EXC_OTHER_ERROR;
+  }
+}
+
+// thrown by a trampoline when the rust code it called panicked.
+class rcpp_rust_panic : public std::runtime_error {
+public:
+  explicit rcpp_rust_panic(const std::string& what) : std::runtime_error(what) {}
+};
+
+// must only be called from inside a catch block.
+static void rcpp_catch(rcpp_exception* exc) {
+  try {
+    throw;
+  } catch (const rcpp_rust_panic& e) {
+    exc->code = RCPP_EXC_RUST_PANIC;
+    exc->what = rcpp_copy_what(e.what());
+  } catch (const CryptoPP::Exception& e) {
+    exc->code = rcpp_error_type_code(e.GetErrorType());
+    exc->what = rcpp_copy_what(e.what());
+  } catch (const std::bad_alloc&) {
+    exc->code = RCPP_EXC_BAD_ALLOC;
+  } catch (const std::exception& e) {
+    exc->code = RCPP_EXC_STD_EXCEPTION;
+    exc->what = rcpp_copy_what(e.what());
+  } catch (...) {
+    exc->code = RCPP_EXC_UNKNOWN;
+  }
+}
+
+// throws the error a rust callback stored in `exc`, if any. the inverse
+// of `rcpp_catch`.
+static void rcpp_rethrow(rcpp_exception* exc) {
+  if (exc->code == RCPP_EXC_NONE) {
+    return;
+  }
+
+  std::string what(exc->what != NULL ? exc->what : "");
+  std::free(exc->what);
+  exc->what = NULL;
+
+  switch (exc->code) {
+    case RCPP_EXC_NOT_IMPLEMENTED:
+      throw CryptoPP::Exception(CryptoPP::Exception::NOT_IMPLEMENTED, what);
+    case RCPP_EXC_INVALID_ARGUMENT:
+      throw CryptoPP::Exception(CryptoPP::Exception::INVALID_ARGUMENT, what);
+    case RCPP_EXC_CANNOT_FLUSH:
+      throw CryptoPP::Exception(CryptoPP::Exception::CANNOT_FLUSH, what);
+    case RCPP_EXC_DATA_INTEGRITY_CHECK_FAILED:
+      throw CryptoPP::Exception(CryptoPP::Exception::DATA_INTEGRITY_CHECK_FAILED, what);
+    case RCPP_EXC_INVALID_DATA_FORMAT:
+      throw CryptoPP::Exception(CryptoPP::Exception::INVALID_DATA_FORMAT, what);
+    case RCPP_EXC_IO_ERROR:
+      throw CryptoPP::Exception(CryptoPP::Exception::IO_ERROR, what);
+    case RCPP_EXC_OTHER_ERROR:
+      throw CryptoPP::Exception(CryptoPP::Exception::OTHER_ERROR, what);
+    case RCPP_EXC_BAD_ALLOC:
+      throw std::bad_alloc();
+    case RCPP_EXC_RUST_PANIC:
+      throw rcpp_rust_panic(what);
+    default:
+      throw std::runtime_error(what);
+  }
+}
+
@@ -0,0 +1,46 @@
+#ifndef RUSTCRYPTOPP_H
+#define RUSTCRYPTOPP_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+enum {
+  RCPP_EXC_NONE = 0,
+  RCPP_EXC_NOT_IMPLEMENTED = 1,
+  RCPP_EXC_INVALID_ARGUMENT = 2,
+  RCPP_EXC_CANNOT_FLUSH = 3,
+  RCPP_EXC_DATA_INTEGRITY_CHECK_FAILED = 4,
+  RCPP_EXC_INVALID_DATA_FORMAT = 5,
+  RCPP_EXC_IO_ERROR = 6,
+  RCPP_EXC_OTHER_ERROR = 7,
+  RCPP_EXC_BAD_ALLOC = 8,
+  RCPP_EXC_STD_EXCEPTION = 9,
+  RCPP_EXC_UNKNOWN = 10,
+  RCPP_EXC_RUST_PANIC = 11,
+};
+
+/* set by a shim when the call throws. `what` is allocated with
+   `malloc` and must be released by the caller. */
+typedef struct rcpp_exception {
+  int code;
+  char* what;
+} rcpp_exception;
+
+/* a std::string. an argument only borrows `data`, the `data` of a
+   returned string is allocated with `malloc` and must be released by
+   the caller. it is not NUL terminated. */
+typedef struct rcpp_string {
+  char* data;
+  size_t len;
+} rcpp_string;
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
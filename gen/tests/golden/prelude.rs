@@ -0,0 +1,149 @@
+
+    pub trait CPPContext {
+      fn mut_ctx(&self) -> *mut c_void;
+      fn ctx(&self) -> *const c_void {
+        self.mut_ctx()
+      }
+    }
+
+    /// implemented by wrappers of classes derived from the bound class
+    /// `B`, gives the address of their `B` subobject.
+    pub trait Upcast<B> : CPPContext {
+      fn mut_base_ctx(&self) -> *mut c_void;
+      fn base_ctx(&self) -> *const c_void {
+        self.mut_base_ctx()
+      }
+    }
+
+    /// out parameter of every shim. `code` is `EXC_NONE` unless the
+    /// shim caught a C++ exception, in which case `what` may hold a
+    /// malloc'd copy of the exception message.
+    #[repr(C)]
+    pub struct Exception {
+      pub code: c_int,
+      pub what: *mut c_char
+    }
+
+    impl Drop for Exception {
+      fn drop(&mut self) {
+        if !self.what.is_null() {
+          unsafe { ::libc::free(self.what as *mut c_void) };
+        }
+      }
+    }
+
+    impl Exception {
+      /// an exception holding a malloc'd copy of `what`, for handing
+      /// over to C++.
+      pub fn new(code: c_int, what: &str) -> Exception {
+        let copy = unsafe { ::libc::malloc((what.len() + 1) as size_t) as *mut c_char };
+        if !copy.is_null() {
+          unsafe {
+            ::std::ptr::copy_nonoverlapping(what.as_ptr() as *const c_char, copy, what.len());
+            *copy.offset(what.len() as isize) = 0;
+          }
+        }
+
+        Exception { code: code, what: copy }
+      }
+    }
+
+    /// calls a shim with a fresh exception slot, handing the slot
+    /// back as an error if the shim caught something.
+    pub fn catch<T, F>(f: F) -> ::std::result::Result<T, Exception>
+        where F: FnOnce(*mut Exception) -> T {
+      let mut exc = Exception { code: EXC_NONE, what: 0 as *mut c_char };
+      let val = f(&mut exc);
+
+      if exc.code == EXC_NONE {
+        Ok(val)
+      } else {
+        Err(exc)
+      }
+    }
+
+    /// runs the Rust side of a call made by a C++ trampoline. an error
+    /// or a panic is stored in `exc` for the trampoline to rethrow.
+    pub fn callback<T, E, F>(exc: *mut Exception, f: F) -> T
+        where T: Default,
+              E: Into<Exception>,
+              F: FnOnce() -> ::std::result::Result<T, E> {
+      let err = match ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(f)) {
+        Ok(Ok(val))  => return val,
+        Ok(Err(err)) => err.into(),
+        Err(payload) => {
+          let what = if let Some(s) = payload.downcast_ref::<&str>() {
+            *s
+          } else if let Some(s) = payload.downcast_ref::<String>() {
+            &s[..]
+          } else {
+            ""
+          };
+
+          Exception::new(EXC_RUST_PANIC, what)
+        }
+      };
+
+      unsafe { ::std::ptr::write(exc, err) };
+      T::default()
+    }
+
+    /// `len` bytes at `ptr`, which may be null if `len` is 0.
+    pub unsafe fn slice<'a>(ptr: *const u8, len: size_t) -> &'a [u8] {
+      if len == 0 {
+        &[]
+      } else {
+        ::std::slice::from_raw_parts(ptr, len as usize)
+      }
+    }
+
+    pub unsafe fn slice_mut<'a>(ptr: *mut u8, len: size_t) -> &'a mut [u8] {
+      if len == 0 {
+        &mut []
+      } else {
+        ::std::slice::from_raw_parts_mut(ptr, len as usize)
+      }
+    }
+
+    /// a `std::string` passed to or returned by a shim. an argument
+    /// borrows its bytes, those of a returned string are malloc'd and
+    /// taken over by `into_vec` or `into_string`.
+    #[repr(C)]
+    pub struct CppString {
+      pub data: *mut c_char,
+      pub len: size_t
+    }
+
+    impl CppString {
+      pub fn borrow(bytes: &[u8]) -> CppString {
+        CppString { data: bytes.as_ptr() as *mut c_char, len: bytes.len() as size_t }
+      }
+
+      pub unsafe fn into_vec(self) -> Vec<u8> {
+        let vec = slice(self.data as *const u8, self.len).to_vec();
+        ::libc::free(self.data as *mut c_void);
+        vec
+      }
+
+      /// invalid UTF-8 is replaced with U+FFFD.
+      pub unsafe fn into_string(self) -> String {
+        match String::from_utf8(self.into_vec()) {
+          Ok(s)  => s,
+          Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned()
+        }
+      }
+    }
+  
+pub const EXC_NONE: c_int = 0;
+pub const EXC_NOT_IMPLEMENTED: c_int = 1;
+pub const EXC_INVALID_ARGUMENT: c_int = 2;
+pub const EXC_CANNOT_FLUSH: c_int = 3;
+pub const EXC_DATA_INTEGRITY_CHECK_FAILED: c_int = 4;
+pub const EXC_INVALID_DATA_FORMAT: c_int = 5;
+pub const EXC_IO_ERROR: c_int = 6;
+pub const EXC_OTHER_ERROR: c_int = 7;
+pub const EXC_BAD_ALLOC: c_int = 8;
+pub const EXC_STD_EXCEPTION: c_int = 9;
+pub const EXC_UNKNOWN: c_int = 10;
+pub const EXC_RUST_PANIC: c_int = 11;
+
@@ -0,0 +1,15 @@
+extern "C"
+void del_Exception(Exception* ctx) {
+  delete ctx;
+}
+
+extern "C"
+char const* mth_Exception_what(Exception const* ctx, rcpp_exception* exc) {
+  try {
+    return ctx->what();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<char const*>();
+  }
+}
+
@@ -0,0 +1,6 @@
+#[allow(non_camel_case_types)]
+pub enum Exception {}
+extern {
+  pub fn mth_Exception_what(ctx: *const c_void, exc: *mut Exception) -> *const c_char;
+  pub fn del_Exception(ctx: *mut c_void);
+}
@@ -0,0 +1,63 @@
+extern "C"
+void del_Integer(Integer* ctx) {
+  delete ctx;
+}
+
+extern "C"
+bool mth_Integer_IsNegative(Integer const* ctx, rcpp_exception* exc) {
+  try {
+    return ctx->IsNegative();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<bool>();
+  }
+}
+
+extern "C"
+byte mth_Integer_GetByte(Integer const* ctx, size_t arg0, rcpp_exception* exc) {
+  try {
+    return ctx->GetByte( arg0);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<byte>();
+  }
+}
+
+extern "C"
+lword mth_Integer_GetBits(Integer const* ctx, size_t arg0, size_t arg1, rcpp_exception* exc) {
+  try {
+    return ctx->GetBits( arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<lword>();
+  }
+}
+
+extern "C"
+long mth_Integer_ConvertToLong(Integer const* ctx, rcpp_exception* exc) {
+  try {
+    return ctx->ConvertToLong();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<long>();
+  }
+}
+
+extern "C"
+void mth_Integer_SetByte(Integer* ctx, size_t arg0, byte arg1, rcpp_exception* exc) {
+  try {
+    ctx->SetByte( arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
+extern "C"
+void mth_Integer_Randomize(Integer* ctx, RandomNumberGenerator* arg0, size_t arg1, rcpp_exception* exc) {
+  try {
+    ctx->Randomize( *arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
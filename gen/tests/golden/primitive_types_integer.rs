@@ -0,0 +1,11 @@
+#[allow(non_camel_case_types)]
+pub enum Integer {}
+extern {
+  pub fn mth_Integer_IsNegative(ctx: *const c_void, exc: *mut Exception) -> bool;
+  pub fn mth_Integer_GetByte(ctx: *const c_void, arg0: size_t, exc: *mut Exception) -> c_uchar;
+  pub fn mth_Integer_GetBits(ctx: *const c_void, arg0: size_t, arg1: size_t, exc: *mut Exception) -> u64;
+  pub fn mth_Integer_ConvertToLong(ctx: *const c_void, exc: *mut Exception) -> c_long;
+  pub fn mth_Integer_SetByte(ctx: *mut c_void, arg0: size_t, arg1: c_uchar, exc: *mut Exception);
+  pub fn mth_Integer_Randomize(ctx: *mut c_void, arg0: *mut c_void, arg1: size_t, exc: *mut Exception);
+  pub fn del_Integer(ctx: *mut c_void);
+}
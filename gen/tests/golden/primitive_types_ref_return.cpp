@@ -0,0 +1,15 @@
+extern "C"
+void del_ModularArithmetic(ModularArithmetic* ctx) {
+  delete ctx;
+}
+
+extern "C"
+Integer const* mth_ModularArithmetic_GetModulus(ModularArithmetic const* ctx, rcpp_exception* exc) {
+  try {
+    return &ctx->GetModulus();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<Integer const*>();
+  }
+}
+
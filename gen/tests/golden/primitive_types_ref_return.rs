@@ -0,0 +1,6 @@
+#[allow(non_camel_case_types)]
+pub enum ModularArithmetic {}
+extern {
+  pub fn mth_ModularArithmetic_GetModulus(ctx: *const c_void, exc: *mut Exception) -> *const c_void;
+  pub fn del_ModularArithmetic(ctx: *mut c_void);
+}
@@ -0,0 +1,15 @@
+extern "C"
+void del_RandomNumberGenerator(RandomNumberGenerator* ctx) {
+  delete ctx;
+}
+
+extern "C"
+word32 mth_RandomNumberGenerator_GenerateWord32(RandomNumberGenerator* ctx, word32 arg0, word32 arg1, rcpp_exception* exc) {
+  try {
+    return ctx->GenerateWord32( arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<word32>();
+  }
+}
+
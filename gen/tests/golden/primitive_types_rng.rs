@@ -0,0 +1,6 @@
+#[allow(non_camel_case_types)]
+pub enum RandomNumberGenerator {}
+extern {
+  pub fn mth_RandomNumberGenerator_GenerateWord32(ctx: *mut c_void, arg0: u32, arg1: u32, exc: *mut Exception) -> u32;
+  pub fn del_RandomNumberGenerator(ctx: *mut c_void);
+}
@@ -0,0 +1,15 @@
+extern "C"
+void del_Timer(Timer* ctx) {
+  delete ctx;
+}
+
+extern "C"
+double mth_Timer_ElapsedTimeAsDouble(Timer* ctx, rcpp_exception* exc) {
+  try {
+    return ctx->ElapsedTimeAsDouble();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<double>();
+  }
+}
+
@@ -0,0 +1,6 @@
+#[allow(non_camel_case_types)]
+pub enum Timer {}
+extern {
+  pub fn mth_Timer_ElapsedTimeAsDouble(ctx: *mut c_void, exc: *mut Exception) -> c_double;
+  pub fn del_Timer(ctx: *mut c_void);
+}
@@ -0,0 +1,9 @@
+#include <cstdio>
+
+int main() {
+  std::printf("%s %s %lld\n", "Integer", "UNSIGNED", static_cast<long long>(Integer::UNSIGNED));
+  std::printf("%s %s %lld\n", "Integer", "SIGNED", static_cast<long long>(Integer::SIGNED));
+  std::printf("%s %s %llu\n", "SHA256", "DIGESTSIZE", static_cast<unsigned long long>(SHA256::DIGESTSIZE));
+  std::printf("%s %s %lld\n", "SHA256", "BLOCKSIZE", static_cast<long long>(SHA256::BLOCKSIZE));
+  return 0;
+}
@@ -0,0 +1,88 @@
+#ifndef RUSTCRYPTOPP_H
+#define RUSTCRYPTOPP_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+enum {
+  RCPP_EXC_NONE = 0,
+  RCPP_EXC_NOT_IMPLEMENTED = 1,
+  RCPP_EXC_INVALID_ARGUMENT = 2,
+  RCPP_EXC_CANNOT_FLUSH = 3,
+  RCPP_EXC_DATA_INTEGRITY_CHECK_FAILED = 4,
+  RCPP_EXC_INVALID_DATA_FORMAT = 5,
+  RCPP_EXC_IO_ERROR = 6,
+  RCPP_EXC_OTHER_ERROR = 7,
+  RCPP_EXC_BAD_ALLOC = 8,
+  RCPP_EXC_STD_EXCEPTION = 9,
+  RCPP_EXC_UNKNOWN = 10,
+  RCPP_EXC_RUST_PANIC = 11,
+};
+
+/* set by a shim when the call throws. `what` is allocated with
+   `malloc` and must be released by the caller. */
+typedef struct rcpp_exception {
+  int code;
+  char* what;
+} rcpp_exception;
+
+/* a std::string. an argument only borrows `data`, the `data` of a
+   returned string is allocated with `malloc` and must be released by
+   the caller. it is not NUL terminated. */
+typedef struct rcpp_string {
+  char* data;
+  size_t len;
+} rcpp_string;
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* CryptoPP::Integer */
+#ifndef RCPP_HANDLE_CryptoPP_SInteger
+#define RCPP_HANDLE_CryptoPP_SInteger
+typedef struct rcpp_CryptoPP_SInteger rcpp_CryptoPP_SInteger;
+#endif
+#ifndef RCPP_HANDLE_RandomNumberGenerator
+#define RCPP_HANDLE_RandomNumberGenerator
+typedef struct rcpp_RandomNumberGenerator rcpp_RandomNumberGenerator;
+#endif
+rcpp_CryptoPP_SInteger* new_CryptoPP_SInteger(rcpp_exception* exc);
+rcpp_CryptoPP_SInteger* new_random_CryptoPP_SInteger(rcpp_RandomNumberGenerator* arg0, size_t arg1, rcpp_exception* exc);
+void del_CryptoPP_SInteger(rcpp_CryptoPP_SInteger* ctx);
+bool mth_CryptoPP_SInteger_IsNegative(rcpp_CryptoPP_SInteger const* ctx, rcpp_exception* exc);
+uint32_t mth_CryptoPP_SInteger_GetBits(rcpp_CryptoPP_SInteger const* ctx, size_t arg0, size_t arg1, rcpp_exception* exc);
+void mth_CryptoPP_SInteger_Encode(rcpp_CryptoPP_SInteger const* ctx, unsigned char* arg0, size_t arg1, rcpp_exception* exc);
+rcpp_CryptoPP_SInteger* mth_CryptoPP_SInteger_Squared(rcpp_CryptoPP_SInteger const* ctx, rcpp_exception* exc);
+rcpp_CryptoPP_SInteger* smth_CryptoPP_SInteger_Power2(size_t arg0, rcpp_exception* exc);
+
+/* RandomNumberGenerator */
+#ifndef RCPP_HANDLE_RandomNumberGenerator
+#define RCPP_HANDLE_RandomNumberGenerator
+typedef struct rcpp_RandomNumberGenerator rcpp_RandomNumberGenerator;
+#endif
+#ifndef RCPP_HANDLE_Algorithm
+#define RCPP_HANDLE_Algorithm
+typedef struct rcpp_Algorithm rcpp_Algorithm;
+#endif
+void del_RandomNumberGenerator(rcpp_RandomNumberGenerator* ctx);
+rcpp_Algorithm* upcast_RandomNumberGenerator_Algorithm(rcpp_RandomNumberGenerator* ctx);
+typedef struct rcpp_vtable_RandomNumberGenerator {
+  void (*GenerateBlock)(void* self, unsigned char* arg0, size_t arg1, rcpp_exception* exc);
+  void (*drop)(void* self);
+} rcpp_vtable_RandomNumberGenerator;
+rcpp_RandomNumberGenerator* new_rust_RandomNumberGenerator(void* self, rcpp_vtable_RandomNumberGenerator const* vtable, rcpp_exception* exc);
+
+/* namespace CryptoPP */
+#ifndef RCPP_HANDLE_CryptoPP_SInteger
+#define RCPP_HANDLE_CryptoPP_SInteger
+typedef struct rcpp_CryptoPP_SInteger rcpp_CryptoPP_SInteger;
+#endif
+rcpp_CryptoPP_SInteger* fn_CryptoPP_a_exp_b_mod_c(rcpp_CryptoPP_SInteger const* arg0, rcpp_CryptoPP_SInteger const* arg1, rcpp_CryptoPP_SInteger const* arg2, rcpp_exception* exc);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,28 @@
+use std;
+
+pub struct Transformation {
+  ctx: *mut c_void
+}
+impl Drop for Transformation {
+  fn drop(&mut self) {
+    unsafe { cpp::del_HashTransformation(self.ctx) };
+  }
+}
+impl Transformation {
+  pub fn final_truncated(&mut self, arg0: &mut [u8]) -> ::Result<()> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_Final_truncated(self.ctx, arg0.as_mut_ptr(), arg0.len() as ::libc::size_t, exc)
+    }));
+
+    Ok(ret)
+  }
+}
+
+impl cpp::CPPContext for Transformation {
+  fn mut_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::HashTransformation> for Transformation {
+  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }
+}
+
@@ -0,0 +1,148 @@
+use std;
+
+pub struct Integer {
+  ctx: *mut c_void
+}
+impl Drop for Integer {
+  fn drop(&mut self) {
+    unsafe { cpp::del_Integer(self.ctx) };
+  }
+}
+impl Integer {
+  pub fn try_new() -> ::Result<Integer> {
+    let ctx = try!(cpp::catch(|exc| unsafe { cpp::new_Integer(exc) }));
+
+    Ok(Integer { ctx: ctx })
+  }
+
+  pub fn new() -> Integer {
+    Integer::try_new().unwrap()
+  }
+
+  pub fn try_copy(arg0: &cpp::Upcast<cpp::Integer>) -> ::Result<Integer> {
+    let ctx = try!(cpp::catch(|exc| unsafe { cpp::new_copy_Integer(cpp::Upcast::base_ctx(arg0), exc) }));
+
+    Ok(Integer { ctx: ctx })
+  }
+
+  pub fn copy(arg0: &cpp::Upcast<cpp::Integer>) -> Integer {
+    Integer::try_copy(arg0).unwrap()
+  }
+
+  pub fn try_from_long(arg0: ::libc::c_long) -> ::Result<Integer> {
+    let ctx = try!(cpp::catch(|exc| unsafe { cpp::new_from_long_Integer(arg0, exc) }));
+
+    Ok(Integer { ctx: ctx })
+  }
+
+  pub fn from_long(arg0: ::libc::c_long) -> Integer {
+    Integer::try_from_long(arg0).unwrap()
+  }
+
+  pub fn try_from_bytes(arg0: &[u8]) -> ::Result<Integer> {
+    let ctx = try!(cpp::catch(|exc| unsafe { cpp::new_from_bytes_Integer(arg0.as_ptr(), arg0.len() as ::libc::size_t, exc) }));
+
+    Ok(Integer { ctx: ctx })
+  }
+
+  pub fn from_bytes(arg0: &[u8]) -> Integer {
+    Integer::try_from_bytes(arg0).unwrap()
+  }
+
+  pub fn is_negative(&self) -> ::Result<bool> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_IsNegative(self.ctx, exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn bit_count(&self) -> ::Result<::libc::c_uint> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_BitCount(self.ctx, exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn min_encoded_size(&self) -> ::Result<::libc::size_t> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_MinEncodedSize(self.ctx, exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn encode(&self, arg0: &mut [u8]) -> ::Result<()> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_Encode(self.ctx, arg0.as_mut_ptr(), arg0.len() as ::libc::size_t, exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn squared(&self) -> ::Result<Integer> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_Squared(self.ctx, exc)
+    }));
+
+    Ok(Integer { ctx: ret })
+  }
+
+  pub fn plus(&self, arg0: &cpp::Upcast<cpp::Integer>) -> ::Result<Integer> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_Plus(self.ctx, cpp::Upcast::base_ctx(arg0), exc)
+    }));
+
+    Ok(Integer { ctx: ret })
+  }
+
+  pub fn decode(&mut self, arg0: &[u8]) -> ::Result<()> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_Decode(self.ctx, arg0.as_ptr(), arg0.len() as ::libc::size_t, exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn swap(&mut self, arg0: &mut cpp::Upcast<cpp::Integer>) -> ::Result<()> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_swap(self.ctx, cpp::Upcast::mut_base_ctx(arg0), exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn randomize(&mut self, arg0: &mut cpp::Upcast<cpp::RandomNumberGenerator>, arg1: ::libc::size_t) -> ::Result<()> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_Integer_Randomize(self.ctx, cpp::Upcast::mut_base_ctx(arg0), arg1, exc)
+    }));
+
+    Ok(ret)
+  }
+
+  pub fn power2(arg0: ::libc::size_t) -> ::Result<Integer> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::smth_Integer_Power2(arg0, exc)
+    }));
+
+    Ok(Integer { ctx: ret })
+  }
+}
+
+impl cpp::CPPContext for Integer {
+  fn mut_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::Integer> for Integer {
+  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl std::clone::Clone for Integer {
+  fn clone(&self) -> Integer { Integer::copy(self) }
+}
+
+impl std::default::Default for Integer {
+  fn default() -> Integer { Integer::new() }
+}
+
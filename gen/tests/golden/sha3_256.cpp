@@ -0,0 +1,15 @@
+extern "C"
+SHA3_256 * new_SHA3__256(rcpp_exception* exc) {
+  try {
+    return new SHA3_256();
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_SHA3__256(SHA3_256* ctx) {
+  delete ctx;
+}
+
@@ -0,0 +1,6 @@
+#[allow(non_camel_case_types)]
+pub enum SHA3__256 {}
+extern {
+  pub fn new_SHA3__256(exc: *mut Exception) -> *mut c_void;
+  pub fn del_SHA3__256(ctx: *mut c_void);
+}
@@ -0,0 +1,34 @@
+extern "C"
+void del_CryptoPP_SSHA256(CryptoPP::SHA256* ctx) {
+  delete ctx;
+}
+
+extern "C"
+char const* smth_CryptoPP_SSHA256_StaticAlgorithmName(rcpp_exception* exc) {
+  try {
+    return CryptoPP::SHA256::StaticAlgorithmName();
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<char const*>();
+  }
+}
+
+extern "C"
+void smth_CryptoPP_SSHA256_InitState(word32* arg0, rcpp_exception* exc) {
+  try {
+    CryptoPP::SHA256::InitState( arg0);
+  } catch (...) {
+    rcpp_catch(exc);
+  }
+}
+
+extern "C"
+bool fn_CryptoPP_VerifyBufsEqual(byte const* arg0, byte const* arg1, size_t arg2, rcpp_exception* exc) {
+  try {
+    return CryptoPP::VerifyBufsEqual( arg0,  arg1,  arg2);
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<bool>();
+  }
+}
+
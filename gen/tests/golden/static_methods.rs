@@ -0,0 +1,8 @@
+#[allow(non_camel_case_types)]
+pub enum CryptoPP_SSHA256 {}
+extern {
+  pub fn del_CryptoPP_SSHA256(ctx: *mut c_void);
+  pub fn smth_CryptoPP_SSHA256_StaticAlgorithmName(exc: *mut Exception) -> *const c_char;
+  pub fn smth_CryptoPP_SSHA256_InitState(arg0: *mut u32, exc: *mut Exception);
+  pub fn fn_CryptoPP_VerifyBufsEqual(arg0: *const c_uchar, arg1: *const c_uchar, arg2: size_t, exc: *mut Exception) -> bool;
+}
@@ -0,0 +1,40 @@
+extern "C"
+StringStore * new_from_string_StringStore(rcpp_string arg0, rcpp_exception* exc) {
+  try {
+    return new StringStore( rcpp_from_string(arg0));
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_StringStore(StringStore* ctx) {
+  delete ctx;
+}
+
+extern "C"
+void del_HashTransformation(HashTransformation* ctx) {
+  delete ctx;
+}
+
+extern "C"
+rcpp_string mth_HashTransformation_AlgorithmName(HashTransformation const* ctx, rcpp_exception* exc) {
+  try {
+    return rcpp_to_string(ctx->AlgorithmName());
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<rcpp_string>();
+  }
+}
+
+extern "C"
+rcpp_string mth_HashTransformation_CalculateDigestString(HashTransformation* ctx, rcpp_string arg0, rcpp_exception* exc) {
+  try {
+    return rcpp_to_string(ctx->CalculateDigestString( rcpp_from_string(arg0)));
+  } catch (...) {
+    rcpp_catch(exc);
+    return rcpp_default<rcpp_string>();
+  }
+}
+
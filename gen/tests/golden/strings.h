@@ -0,0 +1,17 @@
+/* StringStore */
+#ifndef RCPP_HANDLE_StringStore
+#define RCPP_HANDLE_StringStore
+typedef struct rcpp_StringStore rcpp_StringStore;
+#endif
+rcpp_StringStore* new_from_string_StringStore(rcpp_string arg0, rcpp_exception* exc);
+void del_StringStore(rcpp_StringStore* ctx);
+
+/* HashTransformation */
+#ifndef RCPP_HANDLE_HashTransformation
+#define RCPP_HANDLE_HashTransformation
+typedef struct rcpp_HashTransformation rcpp_HashTransformation;
+#endif
+void del_HashTransformation(rcpp_HashTransformation* ctx);
+rcpp_string mth_HashTransformation_AlgorithmName(rcpp_HashTransformation const* ctx, rcpp_exception* exc);
+rcpp_string mth_HashTransformation_CalculateDigestString(rcpp_HashTransformation* ctx, rcpp_string arg0, rcpp_exception* exc);
+
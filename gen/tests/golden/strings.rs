@@ -0,0 +1,13 @@
+#[allow(non_camel_case_types)]
+pub enum StringStore {}
+extern {
+  pub fn new_from_string_StringStore(arg0: CppString, exc: *mut Exception) -> *mut c_void;
+  pub fn del_StringStore(ctx: *mut c_void);
+}
+#[allow(non_camel_case_types)]
+pub enum HashTransformation {}
+extern {
+  pub fn mth_HashTransformation_AlgorithmName(ctx: *const c_void, exc: *mut Exception) -> CppString;
+  pub fn mth_HashTransformation_CalculateDigestString(ctx: *mut c_void, arg0: CppString, exc: *mut Exception) -> CppString;
+  pub fn del_HashTransformation(ctx: *mut c_void);
+}
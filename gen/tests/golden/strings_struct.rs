@@ -0,0 +1,66 @@
+use std;
+
+pub struct StringStore {
+  ctx: *mut c_void
+}
+impl Drop for StringStore {
+  fn drop(&mut self) {
+    unsafe { cpp::del_StringStore(self.ctx) };
+  }
+}
+impl StringStore {
+  pub fn try_from_string(arg0: &[u8]) -> ::Result<StringStore> {
+    let ctx = try!(cpp::catch(|exc| unsafe { cpp::new_from_string_StringStore(cpp::CppString::borrow(arg0), exc) }));
+
+    Ok(StringStore { ctx: ctx })
+  }
+
+  pub fn from_string(arg0: &[u8]) -> StringStore {
+    StringStore::try_from_string(arg0).unwrap()
+  }
+}
+
+impl cpp::CPPContext for StringStore {
+  fn mut_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::StringStore> for StringStore {
+  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+use std;
+
+pub struct Hash {
+  ctx: *mut c_void
+}
+impl Drop for Hash {
+  fn drop(&mut self) {
+    unsafe { cpp::del_HashTransformation(self.ctx) };
+  }
+}
+impl Hash {
+  pub fn algorithm_name(&self) -> ::Result<String> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_AlgorithmName(self.ctx, exc)
+    }));
+
+    Ok(unsafe { ret.into_string() })
+  }
+
+  pub fn calculate_digest_string(&mut self, arg0: &[u8]) -> ::Result<Vec<u8>> {
+    let ret = try!(cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_CalculateDigestString(self.ctx, cpp::CppString::borrow(arg0), exc)
+    }));
+
+    Ok(unsafe { ret.into_vec() })
+  }
+}
+
+impl cpp::CPPContext for Hash {
+  fn mut_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::HashTransformation> for Hash {
+  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }
+}
+
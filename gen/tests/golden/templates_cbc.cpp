@@ -0,0 +1,25 @@
+extern "C"
+CryptoPP::CBC_Mode<AES>::Encryption * new_CryptoPP_SCBC__Mode_LAES_R_SEncryption(rcpp_exception* exc) {
+  try {
+    return new CryptoPP::CBC_Mode<AES>::Encryption();
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+CryptoPP::CBC_Mode<AES>::Encryption * new_with_key_iv_CryptoPP_SCBC__Mode_LAES_R_SEncryption(unsigned char const* arg0, size_t arg1, unsigned char const* arg2, rcpp_exception* exc) {
+  try {
+    return new CryptoPP::CBC_Mode<AES>::Encryption( arg0,  arg1,  arg2);
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_CryptoPP_SCBC__Mode_LAES_R_SEncryption(CryptoPP::CBC_Mode<AES>::Encryption* ctx) {
+  delete ctx;
+}
+
@@ -0,0 +1,7 @@
+#[allow(non_camel_case_types)]
+pub enum CryptoPP_SCBC__Mode_LAES_R_SEncryption {}
+extern {
+  pub fn new_CryptoPP_SCBC__Mode_LAES_R_SEncryption(exc: *mut Exception) -> *mut c_void;
+  pub fn new_with_key_iv_CryptoPP_SCBC__Mode_LAES_R_SEncryption(arg0: *const c_uchar, arg1: size_t, arg2: *const c_uchar, exc: *mut Exception) -> *mut c_void;
+  pub fn del_CryptoPP_SCBC__Mode_LAES_R_SEncryption(ctx: *mut c_void);
+}
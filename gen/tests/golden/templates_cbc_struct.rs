@@ -0,0 +1,34 @@
+use std;
+
+pub struct Encryption {
+  ctx: *mut c_void
+}
+impl Drop for Encryption {
+  fn drop(&mut self) {
+    unsafe { cpp::del_CryptoPP_SCBC__Mode_LAES_R_SEncryption(self.ctx) };
+  }
+}
+impl Encryption {
+  pub fn try_new() -> ::Result<Encryption> {
+    let ctx = try!(cpp::catch(|exc| unsafe { cpp::new_CryptoPP_SCBC__Mode_LAES_R_SEncryption(exc) }));
+
+    Ok(Encryption { ctx: ctx })
+  }
+
+  pub fn new() -> Encryption {
+    Encryption::try_new().unwrap()
+  }
+}
+
+impl cpp::CPPContext for Encryption {
+  fn mut_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl cpp::Upcast<cpp::CryptoPP_SCBC__Mode_LAES_R_SEncryption> for Encryption {
+  fn mut_base_ctx(&self) -> *mut c_void { self.ctx }
+}
+
+impl std::default::Default for Encryption {
+  fn default() -> Encryption { Encryption::new() }
+}
+
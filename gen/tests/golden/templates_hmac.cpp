@@ -0,0 +1,30 @@
+extern "C"
+HMAC<SHA1> * new_with_key_HMAC_LSHA1_R(unsigned char const* arg0, size_t arg1, rcpp_exception* exc) {
+  try {
+    return new HMAC<SHA1>( arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_HMAC_LSHA1_R(HMAC<SHA1>* ctx) {
+  delete ctx;
+}
+
+extern "C"
+HMAC<SHA3_256> * new_with_key_HMAC_LSHA3__256_R(unsigned char const* arg0, size_t arg1, rcpp_exception* exc) {
+  try {
+    return new HMAC<SHA3_256>( arg0,  arg1);
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_HMAC_LSHA3__256_R(HMAC<SHA3_256>* ctx) {
+  delete ctx;
+}
+
@@ -0,0 +1,12 @@
+#[allow(non_camel_case_types)]
+pub enum HMAC_LSHA1_R {}
+extern {
+  pub fn new_with_key_HMAC_LSHA1_R(arg0: *const c_uchar, arg1: size_t, exc: *mut Exception) -> *mut c_void;
+  pub fn del_HMAC_LSHA1_R(ctx: *mut c_void);
+}
+#[allow(non_camel_case_types)]
+pub enum HMAC_LSHA3__256_R {}
+extern {
+  pub fn new_with_key_HMAC_LSHA3__256_R(arg0: *const c_uchar, arg1: size_t, exc: *mut Exception) -> *mut c_void;
+  pub fn del_HMAC_LSHA3__256_R(ctx: *mut c_void);
+}
@@ -0,0 +1,15 @@
+extern "C"
+RSAES<OAEP<SHA256> >::Encryptor * new_RSAES_LOAEP_LSHA256_R_R_SEncryptor(rcpp_exception* exc) {
+  try {
+    return new RSAES<OAEP<SHA256> >::Encryptor();
+  } catch (...) {
+    rcpp_catch(exc);
+    return NULL;
+  }
+}
+
+extern "C"
+void del_RSAES_LOAEP_LSHA256_R_R_SEncryptor(RSAES<OAEP<SHA256> >::Encryptor* ctx) {
+  delete ctx;
+}
+
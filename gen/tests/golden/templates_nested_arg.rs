@@ -0,0 +1,6 @@
+#[allow(non_camel_case_types)]
+pub enum RSAES_LOAEP_LSHA256_R_R_SEncryptor {}
+extern {
+  pub fn new_RSAES_LOAEP_LSHA256_R_R_SEncryptor(exc: *mut Exception) -> *mut c_void;
+  pub fn del_RSAES_LOAEP_LSHA256_R_R_SEncryptor(ctx: *mut c_void);
+}
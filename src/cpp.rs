@@ -1,5 +1,7 @@
 
-use libc::{c_void, c_uchar, c_long, size_t, c_uint};
+#[allow(unused_imports)]
+use libc::{c_void, c_uchar, c_long, c_longlong, size_t, c_uint, c_int,
+           c_char, c_double};
 
 include!(concat!(env!("OUT_DIR"), "/generated_bindings.rs"));
 
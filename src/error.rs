@@ -0,0 +1,158 @@
+use std;
+use std::error;
+use std::fmt;
+use std::ffi::CStr;
+
+use cpp;
+
+/// a C++ exception caught by one of the generated shims. the first
+/// group of variants mirrors `CryptoPP::Exception::ErrorType` and
+/// carries the exception's `what()` message.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Error {
+  NotImplemented(String),
+  InvalidArgument(String),
+  CannotFlush(String),
+  DataIntegrityCheckFailed(String),
+  InvalidDataFormat(String),
+  IO(String),
+  Other(String),
+  /// `std::bad_alloc` was thrown.
+  OutOfMemory,
+  /// a `std::exception` that isnt a `CryptoPP::Exception`.
+  Std(String),
+  /// something that isnt a `std::exception` was thrown.
+  Unknown,
+  /// Rust code called back from C++ panicked.
+  Panic(String)
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl From<cpp::Exception> for Error {
+  fn from(exc: cpp::Exception) -> Error {
+    let what = if exc.what.is_null() {
+      String::new()
+    } else {
+      unsafe { CStr::from_ptr(exc.what) }.to_string_lossy().into_owned()
+    };
+
+    match exc.code {
+      cpp::EXC_NOT_IMPLEMENTED             => Error::NotImplemented(what),
+      cpp::EXC_INVALID_ARGUMENT            => Error::InvalidArgument(what),
+      cpp::EXC_CANNOT_FLUSH                => Error::CannotFlush(what),
+      cpp::EXC_DATA_INTEGRITY_CHECK_FAILED => Error::DataIntegrityCheckFailed(what),
+      cpp::EXC_INVALID_DATA_FORMAT         => Error::InvalidDataFormat(what),
+      cpp::EXC_IO_ERROR                    => Error::IO(what),
+      cpp::EXC_OTHER_ERROR                 => Error::Other(what),
+      cpp::EXC_BAD_ALLOC                   => Error::OutOfMemory,
+      cpp::EXC_STD_EXCEPTION               => Error::Std(what),
+      cpp::EXC_RUST_PANIC                  => Error::Panic(what),
+      _                                    => Error::Unknown
+    }
+  }
+}
+
+/// lets errors of Rust callbacks be rethrown in C++.
+impl From<Error> for cpp::Exception {
+  fn from(err: Error) -> cpp::Exception {
+    use self::Error::*;
+
+    let (code, what) = match err {
+      NotImplemented(s)           => (cpp::EXC_NOT_IMPLEMENTED, s),
+      InvalidArgument(s)          => (cpp::EXC_INVALID_ARGUMENT, s),
+      CannotFlush(s)              => (cpp::EXC_CANNOT_FLUSH, s),
+      DataIntegrityCheckFailed(s) => (cpp::EXC_DATA_INTEGRITY_CHECK_FAILED, s),
+      InvalidDataFormat(s)        => (cpp::EXC_INVALID_DATA_FORMAT, s),
+      IO(s)                       => (cpp::EXC_IO_ERROR, s),
+      Other(s)                    => (cpp::EXC_OTHER_ERROR, s),
+      OutOfMemory                 => (cpp::EXC_BAD_ALLOC, String::new()),
+      Std(s)                      => (cpp::EXC_STD_EXCEPTION, s),
+      Unknown                     => (cpp::EXC_UNKNOWN, String::new()),
+      Panic(s)                    => (cpp::EXC_RUST_PANIC, s),
+    };
+
+    cpp::Exception::new(code, &what)
+  }
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    use self::Error::*;
+
+    match self {
+      &NotImplemented(ref s)           => write!(f, "not implemented: {}", s),
+      &InvalidArgument(ref s)          => write!(f, "invalid argument: {}", s),
+      &CannotFlush(ref s)              => write!(f, "cannot flush: {}", s),
+      &DataIntegrityCheckFailed(ref s) => write!(f, "data integrity check failed: {}", s),
+      &InvalidDataFormat(ref s)        => write!(f, "invalid data format: {}", s),
+      &IO(ref s)                       => write!(f, "io error: {}", s),
+      &Other(ref s)                    => write!(f, "cryptopp error: {}", s),
+      &OutOfMemory                     => write!(f, "out of memory"),
+      &Std(ref s)                      => write!(f, "c++ exception: {}", s),
+      &Unknown                         => write!(f, "unknown c++ exception"),
+      &Panic(ref s)                    => write!(f, "rust callback panicked: {}", s),
+    }
+  }
+}
+
+impl error::Error for Error {
+  fn description(&self) -> &str {
+    use self::Error::*;
+
+    match self {
+      &NotImplemented(ref s)           |
+      &InvalidArgument(ref s)          |
+      &CannotFlush(ref s)              |
+      &DataIntegrityCheckFailed(ref s) |
+      &InvalidDataFormat(ref s)        |
+      &IO(ref s)                       |
+      &Other(ref s)                    |
+      &Std(ref s)                      |
+      &Panic(ref s)                    => s,
+      &OutOfMemory                     => "out of memory",
+      &Unknown                         => "unknown c++ exception",
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use cpp;
+
+  #[test]
+  fn from_exception() {
+    use super::Error;
+
+    let exc = cpp::Exception {
+      code: cpp::EXC_INVALID_ARGUMENT,
+      what: 0 as *mut _
+    };
+    assert_eq!(Error::from(exc), Error::InvalidArgument(String::new()));
+
+    let exc = cpp::Exception {
+      code: cpp::EXC_BAD_ALLOC,
+      what: 0 as *mut _
+    };
+    assert_eq!(Error::from(exc), Error::OutOfMemory);
+  }
+
+  #[test]
+  fn to_exception() {
+    use super::Error;
+
+    let err = Error::InvalidDataFormat(String::from("bad input"));
+    let exc = cpp::Exception::from(err.clone());
+    assert_eq!(exc.code, cpp::EXC_INVALID_DATA_FORMAT);
+    assert_eq!(Error::from(exc), err);
+  }
+
+  #[test]
+  fn catch() {
+    assert_eq!(cpp::catch(|_| 34).ok(), Some(34));
+
+    let res = cpp::catch(|exc| unsafe { (*exc).code = cpp::EXC_OTHER_ERROR });
+    assert_eq!(res.map_err(super::Error::from),
+               Err(super::Error::Other(String::new())));
+  }
+}
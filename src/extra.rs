@@ -0,0 +1,5 @@
+//! structs and traits of the classes described by the spec files
+//! listed in `RUST_CRYPTOPP_EXTRA_SPECS` when the crate was built, one
+//! module per class named after its C path.
+
+include!(concat!(env!("OUT_DIR"), "/extra.rs"));
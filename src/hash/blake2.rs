@@ -0,0 +1,266 @@
+//! BLAKE2b and BLAKE2s of RFC 7693, with a digest of any length up to
+//! 64 and 32 bytes. given a key they are MACs.
+//!
+//! ```ignore
+//! let mut mac = hash::blake2::Params::new().digest_size(32).key(b"secret").blake2b().unwrap();
+//! mac.update(b"message");
+//! let tag = mac.finalize_vec();
+//! ```
+//!
+//! their digests have no fixed size, so rather than `Transformation`
+//! and `Function` they have the same methods of their own.
+
+use std::default::Default;
+use libc::{c_uint, size_t};
+
+use cpp;
+use error::{Error, Result};
+
+mod blake2b {
+  use libc::c_void;
+  use cpp;
+
+  include!(concat!(env!("OUT_DIR"), "/BLAKE2b.rs"));
+}
+
+mod blake2s {
+  use libc::c_void;
+  use cpp;
+
+  include!(concat!(env!("OUT_DIR"), "/BLAKE2s.rs"));
+}
+
+pub use self::blake2b::Blake2b;
+pub use self::blake2s::Blake2s;
+
+/// the sizes in bytes one of the functions allows.
+struct Limits {
+  name:            &'static str,
+  digest_size:     usize,
+  key:             usize,
+  salt:            usize,
+  personalization: usize,
+}
+
+const BLAKE2B: Limits = Limits {
+  name:            "BLAKE2b",
+  digest_size:     64,
+  key:             64,
+  salt:            16,
+  personalization: 16,
+};
+
+const BLAKE2S: Limits = Limits {
+  name:            "BLAKE2s",
+  digest_size:     32,
+  key:             32,
+  salt:            8,
+  personalization: 8,
+};
+
+/// the parameters of a BLAKE2 function: its digest size, by default
+/// the largest, and an optional key, salt and personalization. a salt
+/// or personalization shorter than the function allows is padded with
+/// zeros.
+#[derive(Clone, Debug, Default)]
+pub struct Params {
+  digest_size:     Option<usize>,
+  key:             Vec<u8>,
+  salt:            Vec<u8>,
+  personalization: Vec<u8>,
+}
+
+impl Params {
+  pub fn new() -> Params {
+    Params::default()
+  }
+
+  /// the digest size in bytes.
+  pub fn digest_size(&mut self, size: usize) -> &mut Params {
+    self.digest_size = Some(size);
+    self
+  }
+
+  pub fn key(&mut self, key: &[u8]) -> &mut Params {
+    self.key = key.to_vec();
+    self
+  }
+
+  pub fn salt(&mut self, salt: &[u8]) -> &mut Params {
+    self.salt = salt.to_vec();
+    self
+  }
+
+  pub fn personalization(&mut self, personalization: &[u8]) -> &mut Params {
+    self.personalization = personalization.to_vec();
+    self
+  }
+
+  /// the digest size, after checking the parameters against `limits`.
+  fn check(&self, limits: &Limits) -> Result<c_uint> {
+    let digest_size = self.digest_size.unwrap_or(limits.digest_size);
+    let too_long = |what: &str, len: usize, max: usize| if len > max {
+      Err(Error::InvalidArgument(format!("{}: {} of {} bytes, at most {} allowed",
+                                         limits.name, what, len, max)))
+    } else {
+      Ok(())
+    };
+
+    if digest_size < 1 {
+      return Err(Error::InvalidArgument(format!("{}: empty digest", limits.name)));
+    }
+    try!(too_long("digest", digest_size, limits.digest_size));
+    try!(too_long("key", self.key.len(), limits.key));
+    try!(too_long("salt", self.salt.len(), limits.salt));
+    try!(too_long("personalization", self.personalization.len(), limits.personalization));
+
+    Ok(digest_size as c_uint)
+  }
+
+  pub fn blake2b(&self) -> Result<Blake2b> {
+    let digest_size = try!(self.check(&BLAKE2B));
+    Blake2b::try_with_params(&self.key, &self.salt, &self.personalization, false, digest_size)
+  }
+
+  pub fn blake2s(&self) -> Result<Blake2s> {
+    let digest_size = try!(self.check(&BLAKE2S));
+    Blake2s::try_with_params(&self.key, &self.salt, &self.personalization, false, digest_size)
+  }
+}
+
+macro_rules! impl_blake2 {
+  ($t:ident, $build:ident) => (
+    impl $t {
+      /// updates the function state with input data.
+      pub fn update(&mut self, data: &[u8]) {
+        let ctx = cpp::Upcast::<cpp::HashTransformation>::mut_base_ctx(self);
+        cpp::catch(|exc| unsafe {
+          cpp::mth_HashTransformation_Update(ctx, data.as_ptr(), data.len() as size_t, exc)
+        }).map_err(Error::from).unwrap();
+      }
+
+      /// writes the digest, `digest_size()` bytes, and resets the
+      /// function state. a keyed function keeps its key.
+      pub fn finalize(&mut self, output: &mut [u8]) {
+        assert_eq!(output.len(), self.digest_size());
+
+        let ctx = cpp::Upcast::<cpp::HashTransformation>::mut_base_ctx(self);
+        cpp::catch(|exc| unsafe {
+          cpp::mth_HashTransformation_TruncatedFinal(ctx, output.as_mut_ptr(),
+                                                     output.len() as size_t, exc)
+        }).map_err(Error::from).unwrap();
+      }
+
+      pub fn finalize_vec(&mut self) -> Vec<u8> {
+        let mut output = vec![0; self.digest_size()];
+        self.finalize(&mut output);
+        output
+      }
+
+      // reset function state
+      pub fn reset(&mut self) {
+        let ctx = cpp::Upcast::<cpp::HashTransformation>::mut_base_ctx(self);
+        cpp::catch(|exc| unsafe {
+          cpp::mth_HashTransformation_Restart(ctx, exc)
+        }).map_err(Error::from).unwrap();
+      }
+
+      /// the digest size in bytes.
+      pub fn digest_size(&self) -> usize {
+        let ctx = cpp::Upcast::<cpp::HashTransformation>::base_ctx(self);
+        cpp::catch(|exc| unsafe {
+          cpp::mth_HashTransformation_DigestSize(ctx, exc)
+        }).map_err(Error::from).unwrap() as usize
+      }
+    }
+
+    /// unkeyed, with the largest digest.
+    impl Default for $t {
+      fn default() -> $t {
+        Params::new().$build().unwrap()
+      }
+    }
+  )
+}
+
+impl_blake2!(Blake2b, blake2b);
+impl_blake2!(Blake2s, blake2s);
+
+#[cfg(test)]
+mod test {
+  use Error;
+  use super::{Blake2b, Blake2s, Params};
+
+  fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  #[test]
+  fn rfc7693() {
+    let mut b = Blake2b::default();
+    assert_eq!(b.digest_size(), 64);
+    b.update(b"abc");
+    assert_eq!(hex(&b.finalize_vec()),
+               "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+                7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923");
+
+    let mut s = Blake2s::default();
+    assert_eq!(s.digest_size(), 32);
+    s.update(b"abc");
+    assert_eq!(hex(&s.finalize_vec()),
+               "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982");
+  }
+
+  #[test]
+  fn params() {
+    let mut b = Params::new().digest_size(20).blake2b().unwrap();
+    b.update(b"abc");
+    assert_eq!(hex(&b.finalize_vec()), "384264f676f39536840523f284921cdc68b6846b");
+
+    let mut b = Params::new().salt(b"0123456789abcdef").personalization(b"rust-cryptopp")
+      .blake2b().unwrap();
+    b.update(b"abc");
+    assert_eq!(hex(&b.finalize_vec()),
+               "d350b6196274569393618cd682a9eb2f8e4d918458f2b5075b243e4c71e694fa\
+                725c1f69c4efd95e469ddec6501606126967e94684eacd8669d484bdfc40308b");
+
+    let mut s = Params::new().digest_size(1).salt(b"saltsalt").personalization(b"person")
+      .blake2s().unwrap();
+    s.update(b"abc");
+    assert_eq!(hex(&s.finalize_vec()), "19");
+  }
+
+  #[test]
+  fn mac() {
+    let mut b = Params::new().key(b"secret key").blake2b().unwrap();
+    b.update(b"message");
+    let tag = b.finalize_vec();
+    assert_eq!(hex(&tag),
+               "f1aa846a6dba2e9c51593fc3e083ce210cfadc302df6a4f3d3f6aa0c0e3a6760\
+                7528e898e18adb7717be6ef78291efd58d7c6155c2e62c9401fd0f303a022b4e");
+
+    // the key survives finalizing, and cloning
+    b.update(b"message");
+    assert_eq!(b.clone().finalize_vec(), tag);
+    assert_eq!(b.finalize_vec(), tag);
+
+    let mut s = Params::new().key(b"secret key").digest_size(16).blake2s().unwrap();
+    s.update(b"message");
+    assert_eq!(hex(&s.finalize_vec()), "54d3319eec7c5d1e337447a60ca9b8b5");
+  }
+
+  #[test]
+  fn bad_params() {
+    let bad = |r: Result<Blake2s, Error>| match r {
+      Err(Error::InvalidArgument(_)) => (),
+      _                              => panic!("parameters accepted")
+    };
+
+    bad(Params::new().digest_size(0).blake2s());
+    bad(Params::new().digest_size(33).blake2s());
+    bad(Params::new().key(&[0; 33]).blake2s());
+    bad(Params::new().salt(&[0; 9]).blake2s());
+    bad(Params::new().personalization(&[0; 9]).blake2s());
+    assert!(Params::new().digest_size(64).key(&[0; 64]).blake2b().is_ok());
+  }
+}
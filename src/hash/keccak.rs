@@ -0,0 +1,67 @@
+//! Keccak with its original padding, as used by Ethereum. the digests
+//! differ from the FIPS 202 ones of `hash::sha3`.
+
+define_sized_hash_module!(Keccak__224, h224, 28, Secure);
+define_sized_hash_module!(Keccak__256, h256, 32, Secure);
+define_sized_hash_module!(Keccak__384, h384, 48, Secure);
+define_sized_hash_module!(Keccak__512, h512, 64, Secure);
+
+#[cfg(test)]
+mod test {
+  use hash::DigestSize;
+  use hash::Transformation;
+  use hash::Function;
+  use hash::Digest32;
+
+  fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  #[test]
+  fn sanity() {
+    let mut h256 = super::h256::new();
+    let msg      = b"abc";
+    let expected = Digest32::from_array([
+                     0x4e, 0x03, 0x65, 0x7a, 0xea, 0x45, 0xa9, 0x4f,
+                     0xc7, 0xd4, 0x7b, 0xa8, 0x26, 0xc8, 0xd6, 0x67,
+                     0xc0, 0xd1, 0xe6, 0xe3, 0x3a, 0x64, 0xa0, 0x36,
+                     0xec, 0x44, 0xf5, 0x8f, 0xa1, 0x2d, 0x6c, 0x45
+                   ]);
+
+    assert_eq!(h256.size(), DigestSize::Bits256);
+    assert_eq!(h256.algorithm_name(), "Keccak-256");
+
+    h256.update(msg);
+    assert_eq!(h256.final_digest(), expected);
+
+    let msg2 = b"uchk uchk chk uchk ucka chka chuk";
+    let expected2 = Digest32::from_array([
+                      0xc5, 0x1a, 0x46, 0x40, 0x69, 0x4d, 0x14, 0x91,
+                      0x6a, 0x82, 0xdd, 0xd6, 0x66, 0xd4, 0xea, 0x63,
+                      0x15, 0x87, 0x45, 0xed, 0x99, 0xe6, 0xca, 0xd1,
+                      0x33, 0x1f, 0x39, 0xc5, 0x7e, 0x3a, 0xbe, 0x37
+                    ]);
+
+    assert_eq!(super::h256::digest(msg2), expected2);
+  }
+
+  #[cfg(feature = "sha3")]
+  #[test]
+  fn differs_from_sha3() {
+    assert!(super::h256::digest(b"abc") != ::hash::sha3::h256::digest(b"abc"));
+  }
+
+  #[test]
+  fn known_answers() {
+    assert_eq!(hex(super::h224::digest(b"abc").as_ref()),
+               "c30411768506ebe1c2871b1ee2e87d38df342317300a9b97a95ec6a8");
+    assert_eq!(hex(super::h256::empty_digest().as_ref()),
+               "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+    assert_eq!(hex(super::h384::digest(b"abc").as_ref()),
+               "f7df1165f033337be098e7d288ad6a2f74409d7a60b49c36642218de161b1f99\
+                f8c681e4afaf31a34db29fb763e3c28e");
+    assert_eq!(hex(super::h512::digest(b"abc").as_ref()),
+               "18587dc2ea106b9a1563e32b3312421ca164c7f1f07bc922a9c83d77cea3a1e5\
+                d0c69910739025372dc14ac9642629379540c17e2a65b19d77aa511a9d00bb96");
+  }
+}
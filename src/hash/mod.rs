@@ -1,13 +1,19 @@
 use libc::{size_t};
+use std;
 use std::default::Default;
+use std::io;
 
 use cpp;
+use error::Error;
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum DigestSize {
+  Bits128,
   Bits160,
+  Bits192,
   Bits224,
   Bits256,
+  Bits320,
   Bits384,
   Bits512
 }
@@ -22,9 +28,12 @@ impl DigestSize {
     use self::DigestSize::*;
 
     match self {
+      &Bits128 => 16,
       &Bits160 => 20,
+      &Bits192 => 24,
       &Bits224 => 28,
       &Bits256 => 32,
+      &Bits320 => 40,
       &Bits384 => 48,
       &Bits512 => 64
     }
@@ -36,9 +45,12 @@ impl DigestSize {
 
   fn from_size_in_bits(bits: u32) -> DigestSize {
     match bits {
+      128 => DigestSize::Bits128,
       160 => DigestSize::Bits160,
+      192 => DigestSize::Bits192,
       224 => DigestSize::Bits224,
       256 => DigestSize::Bits256,
+      320 => DigestSize::Bits320,
       384 => DigestSize::Bits384,
       512 => DigestSize::Bits512,
       // if we support a hash that has a size other than
@@ -49,37 +61,55 @@ impl DigestSize {
 }
 
 /// things that satisfy the hash transformation interface.
-pub trait Transformation : cpp::CPPContext {
+///
+/// none of these operations throw for the hash functions bound by
+/// this crate, so a C++ exception is treated as a bug and panics.
+pub trait Transformation : cpp::Upcast<cpp::HashTransformation> {
   /// updates the hash function state with input data.
   fn update(&mut self, data: &[u8]) {
-    unsafe {
-      cpp::mth_HashTransformation_Update(self.mut_ctx(),
+    let ctx = self.mut_base_ctx();
+    cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_Update(ctx,
                                          data.as_ptr(),
-                                         data.len() as size_t)
-    };
+                                         data.len() as size_t,
+                                         exc)
+    }).map_err(Error::from).unwrap();
   }
 
   /// returns the digest and resets the hash function state.
   fn finalize(&mut self, output: &mut [u8]) {
     assert!(output.len() >= self.size().in_bytes() as usize);
 
-    unsafe {
-      cpp::mth_HashTransformation_Final(self.mut_ctx(), output.as_mut_ptr())
-    };
+    let ctx = self.mut_base_ctx();
+    cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_Final(ctx, output.as_mut_ptr(), exc)
+    }).map_err(Error::from).unwrap();
   }
 
   // reset hash function state
   fn reset(&mut self) {
-    unsafe {
-      cpp::mth_HashTransformation_Restart(self.mut_ctx())
-    };
+    let ctx = self.mut_base_ctx();
+    cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_Restart(ctx, exc)
+    }).map_err(Error::from).unwrap();
+  }
+
+  /// the name of the hash function, such as `SHA-1`.
+  fn algorithm_name(&self) -> String {
+    let ctx = self.base_ctx();
+    let name = cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_AlgorithmName(ctx, exc)
+    }).map_err(Error::from).unwrap();
+
+    unsafe { name.into_string() }
   }
 
   /// the digest size.
   fn size(&self) -> DigestSize {
-    DigestSize::from_size_in_bytes(unsafe {
-      cpp::mth_HashTransformation_DigestSize(self.ctx())
-    })
+    let ctx = self.base_ctx();
+    DigestSize::from_size_in_bytes(cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_DigestSize(ctx, exc)
+    }).map_err(Error::from).unwrap())
   }
 }
 
@@ -129,17 +159,25 @@ macro_rules! define_digest_type {
   )
 }
 
+define_digest_type!(digest16, Digest16, 16);
 define_digest_type!(digest20, Digest20, 20);
+define_digest_type!(digest24, Digest24, 24);
 define_digest_type!(digest28, Digest28, 28);
 define_digest_type!(digest32, Digest32, 32);
+define_digest_type!(digest40, Digest40, 40);
 define_digest_type!(digest48, Digest48, 48);
 define_digest_type!(digest64, Digest64, 64);
 
 //this relationship is checked by the `size_sanity` test in generated hash functions
+// the macros below go unused when every hash feature is disabled
+#[allow(unused_macros)]
 macro_rules! size_to_output_type {
+  (16) => (type Output = hash::Digest16;);
   (20) => (type Output = hash::Digest20;);
+  (24) => (type Output = hash::Digest24;);
   (28) => (type Output = hash::Digest28;);
   (32) => (type Output = hash::Digest32;);
+  (40) => (type Output = hash::Digest40;);
   (48) => (type Output = hash::Digest48;);
   (64) => (type Output = hash::Digest64;);
 }
@@ -165,6 +203,145 @@ pub trait Function : Transformation + Default {
   }
 }
 
+/// extendable-output functions, whose output is as long as asked for
+/// rather than a fixed `Digest`.
+///
+/// the output size the Crypto++ object of an implementor is built with
+/// bounds how much can be read, see `XofReader`.
+pub trait Xof : cpp::Upcast<cpp::HashTransformation> + Clone + Default {
+  /// updates the function state with input data.
+  fn update(&mut self, data: &[u8]) {
+    let ctx = self.mut_base_ctx();
+    cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_Update(ctx,
+                                         data.as_ptr(),
+                                         data.len() as size_t,
+                                         exc)
+    }).map_err(Error::from).unwrap();
+  }
+
+  /// resets the function state.
+  fn reset(&mut self) {
+    let ctx = self.mut_base_ctx();
+    cpp::catch(|exc| unsafe {
+      cpp::mth_HashTransformation_Restart(ctx, exc)
+    }).map_err(Error::from).unwrap();
+  }
+
+  /// returns a reader of the output and resets the function state.
+  fn finalize_xof(&mut self) -> XofReader<Self> {
+    let limit = {
+      let ctx = self.base_ctx();
+      cpp::catch(|exc| unsafe {
+        cpp::mth_HashTransformation_DigestSize(ctx, exc)
+      }).map_err(Error::from).unwrap() as usize
+    };
+    let reader = XofReader {
+      state:    self.clone(),
+      output:   Vec::new(),
+      pos:      0,
+      squeezed: 0,
+      limit:    limit,
+    };
+    self.reset();
+
+    reader
+  }
+
+  /// fills `output` with the output for `data`.
+  fn digest_into(data: &[u8], output: &mut [u8]) {
+    let xof = &mut Self::default();
+    xof.update(data);
+    xof.finalize_xof().fill(output);
+  }
+}
+
+/// the output of an `Xof`, read in as many pieces as needed.
+///
+/// the output ends at the output size the Crypto++ object was built
+/// with, 4 GiB less a byte for a `Default` one.
+///
+/// Crypto++ cannot squeeze more output from a finalized state, so the
+/// reader keeps the state from before finalizing and finalizes copies
+/// of it, each time for at least twice the output. only the bytes not
+/// read yet are kept, but squeezing `n` bytes briefly takes `n` bytes
+/// of memory.
+pub struct XofReader<T: Xof> {
+  state:    T,
+  /// the last bytes squeezed, read up to `pos`.
+  output:   Vec<u8>,
+  pos:      usize,
+  /// how many bytes were squeezed, the ones dropped included.
+  squeezed: usize,
+  limit:    usize,
+}
+
+impl<T: Xof> XofReader<T> {
+  /// how many bytes are left to read.
+  pub fn remaining(&self) -> usize {
+    self.limit - self.squeezed + self.output.len() - self.pos
+  }
+
+  /// fills `output` with the next bytes of output, failing with
+  /// `Error::InvalidArgument` and reading nothing past its end.
+  pub fn try_fill(&mut self, output: &mut [u8]) -> Result<(), Error> {
+    if output.len() > self.remaining() {
+      return Err(Error::InvalidArgument(format!("{} bytes asked for, {} left of the output",
+                                                output.len(), self.remaining())));
+    }
+
+    let unread = self.output.len() - self.pos;
+    if output.len() > unread {
+      let end = self.squeezed + output.len() - unread;
+      let len = std::cmp::min(std::cmp::max(end, 2*self.squeezed), self.limit);
+      let mut buf = vec![0; len];
+
+      let state = self.state.clone();
+      let ctx = state.mut_base_ctx();
+      try!(cpp::catch(|exc| unsafe {
+        cpp::mth_HashTransformation_TruncatedFinal(ctx,
+                                                   buf.as_mut_ptr(),
+                                                   buf.len() as size_t,
+                                                   exc)
+      }).map_err(Error::from));
+
+      buf.drain(..self.squeezed - unread);
+      self.output = buf;
+      self.pos = 0;
+      self.squeezed = len;
+    }
+
+    let end = self.pos + output.len();
+    output.copy_from_slice(&self.output[self.pos..end]);
+    self.pos = end;
+
+    Ok(())
+  }
+
+  /// same as `try_fill`, panicking instead.
+  pub fn fill(&mut self, output: &mut [u8]) {
+    self.try_fill(output).unwrap()
+  }
+}
+
+impl<T: Xof> io::Read for XofReader<T> {
+  /// short only at the end of the output.
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    let len = std::cmp::min(buf.len(), self.remaining());
+    try!(self.try_fill(&mut buf[..len]).map_err(|err| io::Error::new(io::ErrorKind::Other, err)));
+    Ok(len)
+  }
+}
+
+/// hash functions fit for new designs. generic code bounded by
+/// `Secure` rather than `Function` refuses the `Insecure` ones at
+/// compile time.
+pub trait Secure : Function {}
+
+/// hash functions that are broken or too short for new designs, bound
+/// to check existing data.
+pub trait Insecure : Function {}
+
 #[cfg(test)]
 mod test {
 
@@ -221,20 +398,26 @@ mod test {
 
 }
 
+#[allow(unused_macros)]
 macro_rules! to_expr { ($e:expr) => ($e) }
 
+// `$cls` is the C path of the class, `SHA3__256` for `SHA3_256`.
+#[allow(unused_macros)]
 macro_rules! define_sized_hash_module {
-  ($file:expr, $modname:ident, $hsize:tt) => (
+  ($cls:ident, $modname:ident, $hsize:tt, $security:ident) => (
     pub mod $modname {
       use cpp;
       use libc::{c_void};
       use hash;
       use hash::{Transformation, Function};
 
-      include!(concat!(env!("OUT_DIR"), $file));
+      include!(concat!(env!("OUT_DIR"), "/consts_", stringify!($cls), ".rs"));
+      include!(concat!(env!("OUT_DIR"), "/", stringify!($cls), ".rs"));
 
       impl Transformation for Hash {}
 
+      impl hash::$security for Hash {}
+
       impl Function for Hash {
         //size_to_arr_digest_result!(u8, $hsize);
         size_to_output_type!($hsize);
@@ -263,6 +446,7 @@ macro_rules! define_sized_hash_module {
 
           let dsize = hash::DigestSize::from_size_in_bytes(to_expr!($hsize));
           assert_eq!(dsize, <Hash as hash::Function>::Output::size());
+          assert_eq!(super::DIGESTSIZE, to_expr!($hsize));
         }
 
         #[test]
@@ -278,5 +462,17 @@ macro_rules! define_sized_hash_module {
 }
 
 // sub-modules must be defined down here to ensure macros are visible
-define_sized_hash_module!("/SHA1.rs", sha1, 20);
+#[cfg(feature = "sha1")]
+define_sized_hash_module!(SHA1, sha1, 20, Insecure);
+#[cfg(feature = "sha2")]
+pub mod sha2;
+#[cfg(feature = "weak-hashes")]
+pub mod weak;
+#[cfg(all(feature = "sha3", cryptopp_5_6_4))]
 pub mod sha3;
+#[cfg(all(feature = "keccak", cryptopp_5_6_4))]
+pub mod keccak;
+#[cfg(all(feature = "shake", cryptopp_8_1))]
+pub mod shake;
+#[cfg(all(feature = "blake2", cryptopp_5_6_4))]
+pub mod blake2;
@@ -0,0 +1,68 @@
+define_sized_hash_module!(SHA224, h224, 28, Secure);
+define_sized_hash_module!(SHA256, h256, 32, Secure);
+define_sized_hash_module!(SHA384, h384, 48, Secure);
+define_sized_hash_module!(SHA512, h512, 64, Secure);
+
+#[cfg(test)]
+mod test {
+  use hash::DigestSize;
+  use hash::Transformation;
+  use hash::Function;
+  use hash::Digest32;
+
+  /// the one and two block messages of the FIPS 180-4 examples.
+  const ABC: &'static [u8] = b"abc";
+  const TWO_BLOCKS_256: &'static [u8] = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+  const TWO_BLOCKS_512: &'static [u8] = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmn\
+                                          hijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+
+  fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  #[test]
+  fn sanity() {
+    let mut h256 = super::h256::new();
+    let expected = Digest32::from_array([
+                     0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea,
+                     0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+                     0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c,
+                     0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad
+                   ]);
+
+    assert_eq!(h256.size(), DigestSize::Bits256);
+    assert_eq!(h256.algorithm_name(), "SHA-256");
+
+    h256.update(ABC);
+    assert_eq!(h256.final_digest(), expected);
+
+    assert_eq!(super::h256::digest(ABC), expected);
+  }
+
+  #[test]
+  fn known_answers() {
+    assert_eq!(hex(super::h224::digest(ABC).as_ref()),
+               "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7");
+    assert_eq!(hex(super::h224::digest(TWO_BLOCKS_256).as_ref()),
+               "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525");
+
+    assert_eq!(hex(super::h256::digest(TWO_BLOCKS_256).as_ref()),
+               "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+    assert_eq!(hex(super::h256::empty_digest().as_ref()),
+               "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+
+    assert_eq!(hex(super::h384::digest(ABC).as_ref()),
+               "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+                8086072ba1e7cc2358baeca134c825a7");
+    assert_eq!(hex(super::h384::digest(TWO_BLOCKS_512).as_ref()),
+               "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712\
+                fcc7c71a557e2db966c3e9fa91746039");
+
+    assert_eq!(hex(super::h512::digest(ABC).as_ref()),
+               "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+                2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f");
+    assert_eq!(hex(super::h512::digest(TWO_BLOCKS_512).as_ref()),
+               "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018\
+                501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909");
+  }
+}
@@ -0,0 +1,108 @@
+//! the SHAKE extendable-output functions of FIPS 202.
+
+use std::default::Default;
+use libc::c_uint;
+
+use hash::Xof;
+
+mod shake128 {
+  use libc::c_void;
+  use cpp;
+
+  include!(concat!(env!("OUT_DIR"), "/SHAKE128.rs"));
+}
+
+mod shake256 {
+  use libc::c_void;
+  use cpp;
+
+  include!(concat!(env!("OUT_DIR"), "/SHAKE256.rs"));
+}
+
+pub use self::shake128::Shake128;
+pub use self::shake256::Shake256;
+
+// the output size only bounds how much can be read, the largest one
+// lets an `XofReader` squeeze as much as it needs.
+impl Default for Shake128 {
+  fn default() -> Shake128 {
+    Shake128::with_output_size(c_uint::max_value())
+  }
+}
+
+impl Default for Shake256 {
+  fn default() -> Shake256 {
+    Shake256::with_output_size(c_uint::max_value())
+  }
+}
+
+impl Xof for Shake128 {}
+impl Xof for Shake256 {}
+
+#[cfg(test)]
+mod test {
+  use std::io::Read;
+  use hash::Xof;
+  use super::{Shake128, Shake256};
+
+  fn hex(output: &[u8]) -> String {
+    output.iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  #[test]
+  fn known_answers() {
+    let mut out = [0u8; 32];
+    Shake128::digest_into(b"", &mut out);
+    assert_eq!(hex(&out), "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26");
+    Shake128::digest_into(b"abc", &mut out);
+    assert_eq!(hex(&out), "5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8");
+
+    let mut out = [0u8; 64];
+    Shake256::digest_into(b"abc", &mut out);
+    assert_eq!(hex(&out), "483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739\
+                           d5a15bef186a5386c75744c0527e1faa9f8726e462a12a4feb06bd8801e751e4");
+  }
+
+  #[test]
+  fn reader() {
+    let mut whole = [0u8; 500];
+    Shake256::digest_into(b"uchk uchk chk", &mut whole);
+
+    let mut xof = Shake256::default();
+    xof.update(b"uchk uchk ");
+    xof.update(b"chk");
+    let mut reader = xof.finalize_xof();
+
+    let mut pieces = [0u8; 500];
+    reader.fill(&mut pieces[..1]);
+    reader.fill(&mut pieces[1..10]);
+    reader.read(&mut pieces[10..300]).unwrap();
+    reader.fill(&mut pieces[300..]);
+    assert_eq!(&pieces[..], &whole[..]);
+
+    // finalizing resets
+    let mut out = [0u8; 32];
+    xof.finalize_xof().fill(&mut out);
+    let mut empty = [0u8; 32];
+    Shake256::digest_into(b"", &mut empty);
+    assert_eq!(out, empty);
+  }
+
+  #[test]
+  fn bounded_reader() {
+    let mut whole = [0u8; 32];
+    Shake128::digest_into(b"abc", &mut whole);
+
+    let mut xof = Shake128::with_output_size(32);
+    xof.update(b"abc");
+    let mut reader = xof.finalize_xof();
+    assert_eq!(reader.remaining(), 32);
+
+    let mut pieces = [0u8; 40];
+    assert_eq!(reader.read(&mut pieces[..20]).unwrap(), 20);
+    assert_eq!(reader.read(&mut pieces[20..]).unwrap(), 12);
+    assert_eq!(reader.read(&mut pieces[32..]).unwrap(), 0);
+    assert_eq!(&pieces[..32], &whole[..]);
+    assert!(reader.try_fill(&mut pieces[..1]).is_err());
+  }
+}
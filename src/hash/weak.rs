@@ -0,0 +1,63 @@
+//! hashes kept for checking old data, all `Insecure`. MD4 and MD5
+//! come from the `Weak` namespace of Crypto++.
+
+define_sized_hash_module!(Weak_SMD4, md4, 16, Insecure);
+define_sized_hash_module!(Weak_SMD5, md5, 16, Insecure);
+define_sized_hash_module!(RIPEMD128, ripemd128, 16, Insecure);
+define_sized_hash_module!(RIPEMD160, ripemd160, 20, Insecure);
+define_sized_hash_module!(RIPEMD256, ripemd256, 32, Insecure);
+define_sized_hash_module!(RIPEMD320, ripemd320, 40, Insecure);
+define_sized_hash_module!(Tiger, tiger, 24, Insecure);
+define_sized_hash_module!(Whirlpool, whirlpool, 64, Insecure);
+
+#[cfg(test)]
+mod test {
+  use hash::DigestSize;
+  use hash::Transformation;
+  use hash::Function;
+
+  fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  #[test]
+  fn sanity() {
+    let mut md5 = super::md5::new();
+    assert_eq!(md5.size(), DigestSize::Bits128);
+    assert_eq!(md5.algorithm_name(), "MD5");
+
+    md5.update(b"abc");
+    assert_eq!(hex(md5.final_digest().as_ref()), "900150983cd24fb0d6963f7d28e17f72");
+    assert_eq!(super::tiger::new().size(), DigestSize::Bits192);
+    assert_eq!(super::ripemd320::new().size(), DigestSize::Bits320);
+  }
+
+  #[test]
+  fn known_answers() {
+    assert_eq!(hex(super::md4::digest(b"abc").as_ref()),
+               "a448017aaf21d8525fc10ae87aa6729d");
+    assert_eq!(hex(super::md5::empty_digest().as_ref()),
+               "d41d8cd98f00b204e9800998ecf8427e");
+    assert_eq!(hex(super::ripemd128::digest(b"abc").as_ref()),
+               "c14a12199c66e4ba84636b0f69144c77");
+    assert_eq!(hex(super::ripemd160::digest(b"abc").as_ref()),
+               "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc");
+    assert_eq!(hex(super::ripemd256::digest(b"abc").as_ref()),
+               "afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65");
+    assert_eq!(hex(super::ripemd320::digest(b"abc").as_ref()),
+               "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82f\
+                a942d64cdbc4682d");
+    assert_eq!(hex(super::tiger::digest(b"abc").as_ref()),
+               "2aab1484e8c158f2bfb8c5ff41b57a525129131c957b5f93");
+    assert_eq!(hex(super::whirlpool::digest(b"abc").as_ref()),
+               "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c\
+                7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5");
+  }
+
+  #[test]
+  fn insecure() {
+    fn check<T: ::hash::Insecure>() {}
+    check::<super::md5::Hash>();
+    check::<super::whirlpool::Hash>();
+  }
+}
@@ -1,36 +1,18 @@
 use libc::{c_void, c_long};
 
 use cpp;
+use error::Result;
 
-pub struct Integer {
-  ctx: *mut c_void
-}
-
-impl Drop for Integer {
-  fn drop(&mut self) {
-    unsafe { cpp::del_Integer(self.ctx) };
-  }
-}
-
-impl Clone for Integer {
-  fn clone(&self) -> Integer {
-    let ctx = unsafe { cpp::new_copy_Integer(self.ctx) };
-    Integer { ctx: ctx }
-  }
-}
+include!(concat!(env!("OUT_DIR"), "/consts_Integer.rs"));
+include!(concat!(env!("OUT_DIR"), "/Integer.rs"));
 
 impl Integer {
-  pub fn new() -> Integer {
-    let ctx = unsafe {
-      cpp::new_Integer()
-    };
-
-    Integer { ctx: ctx }
+  pub fn try_from_i32(val: i32) -> Result<Integer> {
+    Integer::try_from_long(val as c_long)
   }
 
   pub fn from_i32(val: i32) -> Integer {
-    let ctx = unsafe { cpp::new_from_long_Integer(val as c_long) };
-    Integer { ctx: ctx }
+    Integer::try_from_i32(val).unwrap()
   }
 }
 
@@ -45,18 +27,85 @@ mod test {
   fn sanity() {
     let i = super::new();
     println!("i = {:?}", i.ctx);
+    assert!(i.is_zero().unwrap());
 
     let i2 = i.clone();
     println!("i2 = {:?}", i2.ctx);
 
     let i3 = super::Integer::from_i32(34);
     println!("i3 = {:?}", i3.ctx);
+    assert_eq!(i3.convert_to_long().unwrap(), 34);
+  }
+
+  #[test]
+  fn methods() {
+    let i = super::Integer::from_i32(-12);
+    assert!(i.is_negative().unwrap());
+    assert_eq!(i.squared().unwrap().convert_to_long().unwrap(), 144);
+
+    let p = super::Integer::power2(70).unwrap();
+    assert_eq!(p.bit_count().unwrap(), 71);
+  }
+
+  #[test]
+  fn operators() {
+    use Error;
+
+    let a = super::Integer::from_i32(12);
+    let b = super::Integer::from_i32(5);
+
+    assert_eq!((&a + &b).convert_to_long().unwrap(), 17);
+    assert_eq!((&a - &b).convert_to_long().unwrap(), 7);
+    assert_eq!((&a * &b).convert_to_long().unwrap(), 60);
+    assert_eq!((&a / &b).convert_to_long().unwrap(), 2);
+    assert_eq!((&a % &b).convert_to_long().unwrap(), 2);
+    assert_eq!((-&a).convert_to_long().unwrap(), -12);
+    assert_eq!((&a << 2).convert_to_long().unwrap(), 48);
+    assert_eq!((&a >> 2).convert_to_long().unwrap(), 3);
+
+    assert!(b < a);
+    assert!(a == a.clone());
+    assert!(a != b);
+
+    let mut c = a.clone();
+    c += &b;
+    c *= &b;
+    c -= &a;
+    assert_eq!(c.convert_to_long().unwrap(), 73);
+
+    match a.try_div(&super::new()) {
+      Err(Error::Other(_)) => (),
+      res                  => panic!("division by zero gave {:?}", res.map(|_| ()))
+    }
+  }
+
+  #[test]
+  fn encode_decode() {
+    let i = super::Integer::from_i32(0x1234);
+    let mut buf = [0u8; 4];
+    assert_eq!(i.min_encoded_size().unwrap(), 2);
+    i.encode(&mut buf).unwrap();
+    assert_eq!(buf, [0, 0, 0x12, 0x34]);
+
+    let mut j = super::new();
+    j.decode(&buf).unwrap();
+    assert_eq!(j.convert_to_long().unwrap(), 0x1234);
   }
 
-//  #[cfg(test)]
-//  mod ctor {
-//    fn long() {
-//      let i = super::Integer::from_i32();
-//    }
-//  }
+  #[test]
+  fn signedness() {
+    use super::Signedness;
+
+    let i = super::Integer::from_i32(-1);
+    let mut buf = [0u8; 2];
+    assert_eq!(i.min_encoded_size_as(Signedness::Signed).unwrap(), 1);
+    i.encode_as(&mut buf, Signedness::Signed).unwrap();
+    assert_eq!(buf, [0xff, 0xff]);
+
+    let mut j = super::new();
+    j.decode_as(&buf, Signedness::Signed).unwrap();
+    assert_eq!(j.convert_to_long().unwrap(), -1);
+    j.decode_as(&buf, Signedness::Unsigned).unwrap();
+    assert_eq!(j.convert_to_long().unwrap(), 0xffff);
+  }
 }
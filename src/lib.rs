@@ -3,6 +3,14 @@ extern crate libc;
 #[macro_use]
 pub mod arr;
 pub mod hash;
+#[cfg(feature = "integer")]
 pub mod integer;
+pub mod rng;
+pub mod error;
+pub mod extra;
+pub mod version;
 
 mod cpp;
+
+pub use error::{Error, Result};
+pub use version::{version, Version};
@@ -0,0 +1,59 @@
+//! random number generators implemented in Rust, for handing to
+//! Crypto++ functions that take a `RandomNumberGenerator`.
+
+use libc::{c_void};
+
+use cpp;
+
+include!(concat!(env!("OUT_DIR"), "/rust_RandomNumberGenerator.rs"));
+
+#[cfg(all(test, feature = "integer"))]
+mod test {
+  use Error;
+  use integer::Integer;
+
+  struct Ones;
+
+  impl super::RandomNumberGenerator for Ones {
+    fn generate_block(&mut self, output: &mut [u8]) -> ::Result<()> {
+      for b in output.iter_mut() {
+        *b = 0xff;
+      }
+      Ok(())
+    }
+  }
+
+  struct Broken;
+
+  impl super::RandomNumberGenerator for Broken {
+    fn generate_block(&mut self, _: &mut [u8]) -> ::Result<()> {
+      Err(Error::NotImplemented(String::from("no entropy")))
+    }
+  }
+
+  struct Panicky;
+
+  impl super::RandomNumberGenerator for Panicky {
+    fn generate_block(&mut self, _: &mut [u8]) -> ::Result<()> {
+      panic!("out of dice")
+    }
+  }
+
+  #[test]
+  fn deterministic() {
+    let mut rng = super::RustRandomNumberGenerator::new(Ones);
+    let i = Integer::random(&mut rng, 16);
+    assert_eq!(i.convert_to_long().unwrap(), 0xffff);
+  }
+
+  #[test]
+  fn errors() {
+    let mut rng = super::RustRandomNumberGenerator::new(Broken);
+    assert_eq!(Integer::try_random(&mut rng, 16).err(),
+               Some(Error::NotImplemented(String::from("no entropy"))));
+
+    let mut rng = super::RustRandomNumberGenerator::new(Panicky);
+    assert_eq!(Integer::try_random(&mut rng, 16).err(),
+               Some(Error::Panic(String::from("out of dice"))));
+  }
+}
@@ -0,0 +1,59 @@
+//! the version of Crypto++ the crate was built against, and the one
+//! it runs with. a shared library upgraded or downgraded after the
+//! build makes them differ, which `Version::matches` tells.
+
+#[cfg(cryptopp_6_0)]
+use cpp;
+#[cfg(cryptopp_6_0)]
+use error::Error;
+
+include!(concat!(env!("OUT_DIR"), "/version.rs"));
+
+/// versions spelled as in `CRYPTOPP_VERSION`, 870 for 8.7.0.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Version {
+  /// the version of the headers the crate was built with.
+  pub header:  u32,
+  /// the version of the library loaded at run time, as told by
+  /// `LibraryVersion()`. Crypto++ only has it since 6.0.
+  pub library: Option<u32>,
+}
+
+impl Version {
+  /// false when the library loaded at run time is known not to be the
+  /// one the crate was built against.
+  pub fn matches(&self) -> bool {
+    self.library.map_or(true, |library| library == self.header)
+  }
+}
+
+#[cfg(cryptopp_6_0)]
+fn library_version() -> Option<u32> {
+  let version = cpp::catch(|exc| unsafe {
+    cpp::fn_CryptoPP_LibraryVersion(exc)
+  }).map_err(Error::from).unwrap();
+
+  Some(version as u32)
+}
+
+#[cfg(not(cryptopp_6_0))]
+fn library_version() -> Option<u32> {
+  None
+}
+
+pub fn version() -> Version {
+  Version {
+    header:  HEADER_VERSION,
+    library: library_version(),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  #[test]
+  fn sanity() {
+    let v = super::version();
+    assert!(v.header >= 500);
+    assert!(v.matches());
+  }
+}